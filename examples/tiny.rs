@@ -95,6 +95,9 @@ struct Styles {
     count: Style,
     top_frame: Style,
     search_button: Style,
+    list: Style,
+    tooltip: Style,
+    dialog: Style,
 }
 
 impl Styles {
@@ -169,6 +172,24 @@ impl Styles {
                 .frame_color(vec4(91.4 / 100.0, 94.5 / 100.0, 95.3 / 100.0, 1.0))
                 .vert_align(VertAlign::Center)
                 .margin(Spacing::x(16.0)),
+            list: base
+                .frame_style(FrameStyle::None)
+                .frame_color(vec4(91.4 / 100.0, 94.5 / 100.0, 95.3 / 100.0, 1.0))
+                .inactive_color(GRAY),
+            tooltip: base
+                .frame_style(FrameStyle::RoundedRectangle(5.0))
+                .frame_color(vec4(0.2, 0.2, 0.2, 0.9))
+                .text_color(vec4(1.0, 1.0, 1.0, 1.0))
+                .text_height(15.0)
+                .padding(Spacing::symmetrical(5.0)),
+            dialog: base
+                .align(Align::Center)
+                .frame_color(vec4(1.0, 1.0, 1.0, 1.0))
+                .text_color(vec4(0.0, 0.0, 0.0, 1.0))
+                .text_height(26.0)
+                .shadow_color(vec4(0.0, 0.0, 0.0, 0.2))
+                .shadow_dir(vec2(4.0, -4.0))
+                .padding(Spacing::symmetrical(20.0)),
         }
     }
 }
@@ -193,6 +214,8 @@ struct State {
     show_platforms: bool,
 
     search: TextEdit,
+
+    disconnect_prompt: Option<String>,
 }
 
 impl State {
@@ -212,7 +235,8 @@ impl State {
             show_packers: true,
             show_bagers: true,
             show_platforms: true,
-            search: TextEdit::new("Search", 30),
+            search: TextEdit::new("", 30).placeholder("Search"),
+            disconnect_prompt: None,
         }
     }
 
@@ -377,108 +401,170 @@ impl State {
 
                     ui.next_line();
 
-                    for robot in filtered_robots {
-                        ui.frame(self.style.back_frame, |ui| {
-                            ui.frame(self.style.frame, |ui| {
-                                ui.with_style(
-                                    self.style.frame.margin(Spacing::symmetrical(5.0)),
-                                    |ui| {
-                                        ui.rounded_rectangle(
-                                            vec2(
-                                                self.style.frame.text_height / 10.0,
-                                                self.style.frame.text_height,
-                                            ),
-                                            self.style.frame.text_height / 20.0,
-                                            robot.connection_state.color(),
-                                        );
-                                        ui.image(self.robot.clone());
-
-                                        ui.sized_area(vec2(360.0, 0.0), self.style.label, |ui| {
-                                            ui.text(robot.name.clone());
-                                            ui.next_line();
-                                            ui.with_style(self.style.label_sub, |ui| {
-                                                ui.text(format!("ID: {}", robot.id));
+                    ui.scroll_area(vec2(0.0, 700.0), self.style.list, |ui| {
+                        for robot in filtered_robots {
+                            ui.id_source(&robot.id).frame(self.style.back_frame, |ui| {
+                                ui.frame(self.style.frame, |ui| {
+                                    ui.with_style(
+                                        self.style.frame.margin(Spacing::symmetrical(5.0)),
+                                        |ui| {
+                                            ui.rounded_rectangle(
+                                                vec2(
+                                                    self.style.frame.text_height / 10.0,
+                                                    self.style.frame.text_height,
+                                                ),
+                                                self.style.frame.text_height / 20.0,
+                                                robot.connection_state.color(),
+                                            );
+                                            ui.image(self.robot.clone());
+
+                                            ui.sized_area(
+                                                vec2(360.0, 0.0),
+                                                self.style.label,
+                                                |ui| {
+                                                    ui.text(robot.name.clone());
+                                                    ui.next_line();
+                                                    ui.with_style(self.style.label_sub, |ui| {
+                                                        ui.text(format!("ID: {}", robot.id));
+                                                    });
+                                                },
+                                            );
+
+                                            ui.area(self.style.icon, |ui| {
+                                                let tooltip = self.style.tooltip;
+
+                                                let camera = ui.image_button(self.camera.clone());
+                                                if camera.pressed {
+                                                    println!("Camera");
+                                                }
+                                                ui.with_style(tooltip, |ui| {
+                                                    ui.tooltip(&camera, "Open camera feed")
+                                                });
+
+                                                let compass = ui.image_button(self.compass.clone());
+                                                if compass.pressed {
+                                                    println!("Compass");
+                                                }
+                                                ui.with_style(tooltip, |ui| {
+                                                    ui.tooltip(&compass, "Show robot location")
+                                                });
+
+                                                let person = ui.image_button(self.person.clone());
+                                                if person.pressed {
+                                                    println!("Person");
+                                                }
+                                                ui.with_style(tooltip, |ui| {
+                                                    ui.tooltip(&person, "Assigned operator")
+                                                });
                                             });
-                                        });
-
-                                        ui.area(self.style.icon, |ui| {
-                                            if ui.image_button(self.camera.clone()).pressed {
-                                                println!("Camera");
-                                            }
-                                            if ui.image_button(self.compass.clone()).pressed {
-                                                println!("Compass");
-                                            }
-                                            if ui.image_button(self.person.clone()).pressed {
-                                                println!("Person");
-                                            }
-                                        });
-
-                                        ui.horizontal_spring();
-
-                                        ui.with_style(self.style.button, |ui| {
-                                            if ui.sized_button(vec2(120.0, 30.0), "Logs").pressed {
-                                                println!("Show Logs");
-                                            }
-                                        });
-
-                                        ui.with_style(
-                                            self.style
-                                                .button
-                                                .frame_color(robot.connection_state.color()),
-                                            |ui| {
+
+                                            ui.horizontal_spring();
+
+                                            ui.with_style(self.style.button, |ui| {
                                                 if ui
-                                                    .sized_button(
-                                                        vec2(120.0, 30.0),
-                                                        match robot.connection_state {
-                                                            ConnectionState::Available => "Connect",
-                                                            ConnectionState::Occupied => {
-                                                                "Disconnect"
-                                                            }
-                                                            ConnectionState::Unavailable => {
-                                                                "Unavailable"
-                                                            }
-                                                        },
-                                                    )
+                                                    .sized_button(vec2(120.0, 30.0), "Logs")
                                                     .pressed
                                                 {
-                                                    println!("State: {:?}", robot.connection_state);
+                                                    println!("Show Logs");
                                                 }
-                                            },
-                                        );
+                                            });
 
-                                        ui.with_style(self.style.frame, |ui| {
-                                            ui.separator();
-                                        });
+                                            ui.with_style(
+                                                self.style
+                                                    .button
+                                                    .frame_color(robot.connection_state.color()),
+                                                |ui| {
+                                                    if ui
+                                                        .sized_button(
+                                                            vec2(120.0, 30.0),
+                                                            match robot.connection_state {
+                                                                ConnectionState::Available => {
+                                                                    "Connect"
+                                                                }
+                                                                ConnectionState::Occupied => {
+                                                                    "Disconnect"
+                                                                }
+                                                                ConnectionState::Unavailable => {
+                                                                    "Unavailable"
+                                                                }
+                                                            },
+                                                        )
+                                                        .pressed
+                                                    {
+                                                        if robot.connection_state
+                                                            == ConnectionState::Occupied
+                                                        {
+                                                            self.disconnect_prompt = Some(format!(
+                                                                "{} {}",
+                                                                robot.name, robot.id
+                                                            ));
+                                                        } else {
+                                                            println!(
+                                                                "State: {:?}",
+                                                                robot.connection_state
+                                                            );
+                                                        }
+                                                    }
+                                                },
+                                            );
+
+                                            ui.with_style(self.style.frame, |ui| {
+                                                ui.separator();
+                                            });
 
-                                        ui.with_style(self.style.icon, |ui| {
-                                            latency_widget(ui, robot.latency);
+                                            ui.with_style(self.style.icon, |ui| {
+                                                latency_widget(ui, robot.latency);
 
-                                            if robot.warning {
-                                                ui.image(self.warning.clone());
-                                            } else {
-                                                ui.image(self.no_warning.clone());
-                                            }
-                                        });
+                                                if robot.warning {
+                                                    ui.image(self.warning.clone());
+                                                } else {
+                                                    ui.image(self.no_warning.clone());
+                                                }
+                                            });
 
-                                        ui.empty_area(vec2(5.0, 0.0));
+                                            ui.empty_area(vec2(5.0, 0.0));
+                                        },
+                                    );
+                                });
+
+                                ui.with_style(
+                                    self.style
+                                        .back_frame
+                                        .margin(Spacing::x(5.0).with_top(5.0))
+                                        .no_shadow(),
+                                    |ui| {
+                                        ui.text(":");
                                     },
                                 );
                             });
 
-                            ui.with_style(
-                                self.style
-                                    .back_frame
-                                    .margin(Spacing::x(5.0).with_top(5.0))
-                                    .no_shadow(),
-                                |ui| {
-                                    ui.text(":");
-                                },
-                            );
-                        });
+                            ui.next_line();
+                        }
+                    });
+                });
+
+                if let Some(robot) = self.disconnect_prompt.clone() {
+                    let mut answered = false;
 
+                    let open = ui.modal("disconnect", self.style.dialog, |ui| {
+                        ui.text(format!("Really disconnect {robot}?"));
                         ui.next_line();
+
+                        ui.with_style(self.style.button, |ui| {
+                            if ui.sized_button(vec2(120.0, 30.0), "Disconnect").pressed {
+                                println!("Disconnect {robot}");
+                                answered = true;
+                            }
+                            if ui.sized_button(vec2(120.0, 30.0), "Cancel").pressed {
+                                answered = true;
+                            }
+                        });
+                    });
+
+                    if answered || !open {
+                        self.disconnect_prompt = None;
                     }
-                });
+                }
             });
     }
 }
@@ -0,0 +1,129 @@
+use crate::{
+    math::{Vec2, Vec4},
+    ui::{id::Id, Ui},
+};
+use std::time::Instant;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum Easing {
+    #[default]
+    Linear,
+    EaseIn,
+    EaseOut,
+    EaseInOut,
+    /// Overshoots the target a little before settling.
+    EaseOutBack,
+}
+
+impl Easing {
+    /// Maps the elapsed fraction `t` in 0..=1 to the fraction of the way to the target.
+    pub fn apply(self, t: f32) -> f32 {
+        let t = t.clamp(0.0, 1.0);
+
+        match self {
+            Easing::Linear => t,
+            Easing::EaseIn => t * t * t,
+            Easing::EaseOut => 1.0 - (1.0 - t).powi(3),
+            Easing::EaseInOut => {
+                if t < 0.5 {
+                    4.0 * t * t * t
+                } else {
+                    1.0 - (-2.0 * t + 2.0).powi(3) / 2.0
+                }
+            }
+            Easing::EaseOutBack => {
+                const C1: f32 = 1.70158;
+                const C3: f32 = C1 + 1.0;
+
+                1.0 + C3 * (t - 1.0).powi(3) + C1 * (t - 1.0).powi(2)
+            }
+        }
+    }
+}
+
+pub trait Animate: Copy + PartialEq + 'static {
+    fn lerp(self, to: Self, t: f32) -> Self;
+}
+
+impl Animate for f32 {
+    #[inline]
+    fn lerp(self, to: Self, t: f32) -> Self {
+        self + (to - self) * t
+    }
+}
+
+impl Animate for Vec2 {
+    #[inline]
+    fn lerp(self, to: Self, t: f32) -> Self {
+        Vec2::lerp(self, to, t)
+    }
+}
+
+impl Animate for Vec4 {
+    #[inline]
+    fn lerp(self, to: Self, t: f32) -> Self {
+        Vec4::lerp(self, to, t)
+    }
+}
+
+struct Animation<T> {
+    from: T,
+    to: T,
+    start: Instant,
+    duration: f32,
+    easing: Easing,
+}
+
+impl<T: Animate> Animation<T> {
+    /// The value at `now` and whether the animation has reached its target.
+    fn sample(&self, now: Instant) -> (T, bool) {
+        let t = if self.duration > 0.0 {
+            (now - self.start).as_secs_f32() / self.duration
+        } else {
+            1.0
+        };
+
+        if t >= 1.0 {
+            (self.to, true)
+        } else {
+            (self.from.lerp(self.to, self.easing.apply(t)), false)
+        }
+    }
+}
+
+pub(crate) fn animate_value<T: Animate>(
+    ui: &mut Ui,
+    id: Id,
+    target: T,
+    duration: f32,
+    easing: Easing,
+) -> T {
+    let now = Instant::now();
+
+    let animation = ui.memory.get_or_insert_with(id, || Animation {
+        from: target,
+        to: target,
+        start: now,
+        duration,
+        easing,
+    });
+
+    // A new target starts from wherever the previous animation had got to
+    if animation.to != target {
+        *animation = Animation {
+            from: animation.sample(now).0,
+            to: target,
+            start: now,
+            duration,
+            easing,
+        };
+    }
+
+    let (value, done) = animation.sample(now);
+
+    if !done {
+        ui.state.animating = true;
+    }
+
+    value
+}
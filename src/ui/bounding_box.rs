@@ -20,4 +20,21 @@ impl BoundingBox {
             && p.y <= self.top_left.y
             && p.y >= bottom_right.y
     }
+
+    #[inline]
+    pub fn clip(&self, other: &BoundingBox) -> Option<BoundingBox> {
+        let left = self.top_left.x.max(other.top_left.x);
+        let top = self.top_left.y.min(other.top_left.y);
+        let right = (self.top_left.x + self.size.x).min(other.top_left.x + other.size.x);
+        let bottom = (self.top_left.y - self.size.y).max(other.top_left.y - other.size.y);
+
+        if right > left && top > bottom {
+            Some(BoundingBox::new(
+                vec2(left, top),
+                vec2(right - left, top - bottom),
+            ))
+        } else {
+            None
+        }
+    }
 }
@@ -0,0 +1,116 @@
+use crate::{
+    math::{vec2, Vec2, Vec4},
+    ui::{
+        color::{held_color, hover_color},
+        draw_api::DrawApi,
+        frame,
+        id::Id,
+        Response, Spacing, Ui,
+    },
+};
+use std::{borrow::Cow, hash::Hash};
+
+pub(crate) fn show<'a>(
+    ui: &mut Ui<'a, '_, '_>,
+    id_source: impl Hash,
+    title: Cow<'a, str>,
+    func: impl FnOnce(&mut Ui),
+) -> Response {
+    let style = ui.style;
+    let id = ui.parent_id.with_child(Id::new(id_source));
+    let title = title.into_owned();
+
+    let mut response = ui.focusable_response_for(id, ui.widget_response_for(id));
+
+    let mut open = *ui.memory.get_persisted_or_default::<bool>(id);
+
+    if response.pressed {
+        open = !open;
+        response.changed = true;
+        ui.memory.insert(id, open);
+    }
+
+    let header_style = if response.held {
+        style.frame_color(held_color(style.frame_color))
+    } else if response.hovered {
+        style.frame_color(hover_color(style.frame_color))
+    } else {
+        style
+    };
+
+    let foreground = if style.enabled {
+        style.text_color
+    } else {
+        style.inactive_color
+    };
+
+    let area_style = style.margin(Spacing::ZERO).padding(Spacing::ZERO);
+
+    frame::show(ui, true, area_style, None, None, true, |ui| {
+        let header = frame::build(
+            ui,
+            false,
+            header_style,
+            None,
+            style.enabled.then_some(id),
+            id,
+            true,
+            |ui| {
+                ui.canvas(
+                    vec2(style.text_height, style.text_height),
+                    move |draw, cursor, size| {
+                        draw_arrow(draw, cursor, size, open, foreground);
+                    },
+                );
+                ui.colored_text(title, foreground);
+                ui.horizontal_spring();
+            },
+        );
+
+        ui.current_line.push(header);
+
+        if open {
+            ui.next_line();
+            ui.empty_area(vec2(style.text_height, 0.0));
+
+            let body = frame::build(
+                ui,
+                true,
+                style,
+                None,
+                None,
+                id.with_child(Id::new("body")),
+                true,
+                func,
+            );
+
+            ui.current_line.push(body);
+        }
+    });
+
+    response
+}
+
+/// Chevron pointing right when collapsed and down when open, centered in `size`.
+pub(crate) fn draw_arrow(draw: &DrawApi, cursor: Vec2, size: Vec2, open: bool, color: Vec4) {
+    let extent = size.x.min(size.y) * 0.4;
+    let center = cursor + vec2(size.x / 2.0, -size.y / 2.0);
+    let width = extent / 5.0;
+
+    let (from, tip, to) = if open {
+        (
+            vec2(-extent / 2.0, extent / 4.0),
+            vec2(0.0, -extent / 4.0),
+            vec2(extent / 2.0, extent / 4.0),
+        )
+    } else {
+        (
+            vec2(-extent / 4.0, extent / 2.0),
+            vec2(extent / 4.0, 0.0),
+            vec2(-extent / 4.0, -extent / 2.0),
+        )
+    };
+
+    draw.line(center + from, center + tip, width, color);
+    draw.line(center + tip, center + to, width, color);
+}
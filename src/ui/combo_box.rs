@@ -0,0 +1,130 @@
+use crate::{
+    math::vec2,
+    ui::{
+        color::{held_color, hover_color},
+        frame,
+        id::Id,
+        popup::{self, Placement},
+        Response, Ui,
+    },
+    window::{Key, KeyRepeat},
+};
+use std::hash::Hash;
+
+pub(crate) fn show(
+    ui: &mut Ui,
+    id_source: impl Hash,
+    selected: &mut usize,
+    options: &[impl AsRef<str>],
+) -> Response {
+    let style = ui.style;
+    let id = ui.parent_id.with_child(Id::new(id_source));
+    let popup_id = id.with_child(Id::new("popup"));
+
+    let mut response = ui.focusable_response_for(id, ui.widget_response_for(id));
+
+    let mut open = style.enabled && ui.state.open_popup == Some(id);
+
+    if response.pressed {
+        open = !open;
+    } else if open {
+        let outside_click = ui.state.mouse_just_pressed && !ui.response_for(popup_id).hovered;
+
+        if outside_click || ui.window.is_key_pressed(Key::Escape, KeyRepeat::No) {
+            open = false;
+        }
+    }
+
+    let button_style = if response.held || open {
+        style.frame_color(held_color(style.frame_color))
+    } else if response.hovered {
+        style.frame_color(hover_color(style.frame_color))
+    } else {
+        style
+    };
+
+    let foreground = if style.enabled {
+        style.text_color
+    } else {
+        style.inactive_color
+    };
+
+    let text = options
+        .get(*selected)
+        .map(|option| option.as_ref().to_owned())
+        .unwrap_or_default();
+
+    let mut button = frame::build(
+        ui,
+        false,
+        button_style,
+        None,
+        style.enabled.then_some(id),
+        id,
+        false,
+        |ui| {
+            ui.colored_text(text, foreground);
+            ui.horizontal_spring();
+
+            let chevron_size = vec2(style.text_height, style.text_height) * 0.5;
+
+            ui.canvas(
+                vec2(chevron_size.x, style.text_height),
+                move |draw, cursor, size| {
+                    let top = cursor - vec2(0.0, (size.y - chevron_size.y) / 2.0);
+                    let width = chevron_size.y / 5.0;
+
+                    draw.line(
+                        top,
+                        top + vec2(chevron_size.x / 2.0, -chevron_size.y * 0.6),
+                        width,
+                        foreground,
+                    );
+                    draw.line(
+                        top + vec2(chevron_size.x / 2.0, -chevron_size.y * 0.6),
+                        top + vec2(chevron_size.x, 0.0),
+                        width,
+                        foreground,
+                    );
+                },
+            );
+        },
+    );
+
+    let anchor = popup::track_anchor(&mut button, &button_style);
+
+    ui.current_line.push(button);
+
+    if open {
+        popup::show(
+            ui,
+            popup_id,
+            Placement::Anchored(anchor),
+            true,
+            style,
+            |ui| {
+                for (index, option) in options.iter().enumerate() {
+                    let row = ui.selectable_label(index == *selected, option.as_ref().to_owned());
+
+                    if row.pressed {
+                        if *selected != index {
+                            *selected = index;
+                            response.changed = true;
+                        }
+                        open = false;
+                    }
+
+                    ui.next_line();
+                }
+            },
+        );
+    }
+
+    if open {
+        ui.state.open_popup = Some(id);
+    } else if ui.state.open_popup == Some(id) {
+        ui.state.open_popup = None;
+    }
+
+    response
+}
@@ -0,0 +1,165 @@
+use crate::{
+    ui::{
+        color::{held_color, hover_color},
+        frame,
+        slider::{auto_decimals, clamp_range, format_value, snap, value_edit, ValueEdit},
+        Numeric, Response, Ui,
+    },
+    window::{Key, KeyRepeat},
+};
+use std::ops::RangeInclusive;
+
+#[must_use = "You should call .show()"]
+pub struct DragValue<'v, T: Numeric> {
+    value: &'v mut T,
+    range: Option<RangeInclusive<T>>,
+    speed: f64,
+    step: Option<f64>,
+    logarithmic: bool,
+    decimals: Option<usize>,
+    suffix: String,
+}
+
+impl<'v, T: Numeric> DragValue<'v, T> {
+    #[inline]
+    pub fn new(value: &'v mut T) -> DragValue<'v, T> {
+        DragValue {
+            value,
+            range: None,
+            speed: 1.0,
+            step: None,
+            logarithmic: false,
+            decimals: None,
+            suffix: String::new(),
+        }
+    }
+
+    #[inline]
+    pub fn range(mut self, range: RangeInclusive<T>) -> DragValue<'v, T> {
+        self.range = Some(range);
+        self
+    }
+
+    /// Change in value per pixel dragged. When logarithmic this is instead
+    /// the change in percent per pixel.
+    #[inline]
+    pub fn speed(mut self, speed: f64) -> DragValue<'v, T> {
+        self.speed = speed;
+        self
+    }
+
+    #[inline]
+    pub fn step(mut self, step: f64) -> DragValue<'v, T> {
+        self.step = Some(step);
+        self
+    }
+
+    #[inline]
+    pub fn logarithmic(mut self, logarithmic: bool) -> DragValue<'v, T> {
+        self.logarithmic = logarithmic;
+        self
+    }
+
+    #[inline]
+    pub fn decimals(mut self, decimals: usize) -> DragValue<'v, T> {
+        self.decimals = Some(decimals);
+        self
+    }
+
+    #[inline]
+    pub fn suffix(mut self, suffix: impl Into<String>) -> DragValue<'v, T> {
+        self.suffix = suffix.into();
+        self
+    }
+
+    pub fn show(self, ui: &mut Ui) -> Response {
+        let style = ui.style;
+        let (min, max) = self
+            .range
+            .as_ref()
+            .map(|range| {
+                let (start, end) = (range.start().to_f64(), range.end().to_f64());
+
+                (start.min(end), start.max(end))
+            })
+            .unwrap_or((f64::NEG_INFINITY, f64::INFINITY));
+        let origin = if min.is_finite() { min } else { 0.0 };
+
+        let id = ui.current_id();
+        let mut response = ui.widget_response();
+        let editing = matches!(ui.state.value_edit, Some((edit_id, _)) if edit_id == id);
+        let dragging = style.enabled && !editing && ui.is_dragging(id);
+
+        let old_value = self.value.to_f64();
+        let mut value = old_value;
+
+        if response.pressed && !editing {
+            ui.state.drag_start_value = value;
+        }
+
+        if dragging && !response.pressed {
+            let start = ui.state.drag_start_value;
+            let delta = (ui.state.mouse_pos.x - ui.state.mouse_pressed_pos.x) as f64;
+
+            let dragged = if self.logarithmic && start > 0.0 {
+                start * (delta * self.speed / 100.0).exp()
+            } else {
+                start + delta * self.speed
+            };
+
+            value = clamp_range(snap(dragged, self.step, origin), min, max);
+        }
+
+        response.focused = ui.focusable(id, response.pressed);
+
+        // Enter on the focused value starts typing an exact number
+        let enter = ui.window.is_key_pressed(Key::Enter, KeyRepeat::No)
+            || ui.window.is_key_pressed(Key::NumPadEnter, KeyRepeat::No);
+        let start_edit = response.double_clicked || (response.focused && !editing && enter);
+
+        let decimals = self.decimals.unwrap_or_else(|| auto_decimals(self.step));
+        let text = format_value(value, decimals);
+
+        match value_edit(ui, id, &text, start_edit) {
+            ValueEdit::Editing => {}
+            ValueEdit::Committed(committed) => {
+                value = clamp_range(snap(committed, self.step, origin), min, max);
+                drag_frame(ui, format_value(value, decimals) + &self.suffix, response);
+            }
+            ValueEdit::Inactive => {
+                drag_frame(ui, text + &self.suffix, response);
+            }
+        }
+
+        if value != old_value {
+            *self.value = T::from_f64(value);
+            response.changed = true;
+        }
+
+        response
+    }
+}
+
+fn drag_frame(ui: &mut Ui, text: String, response: Response) {
+    let style = ui.style;
+
+    let style = if !style.enabled {
+        style.text_color(style.inactive_color)
+    } else if response.held {
+        style.frame_color(held_color(style.frame_color))
+    } else if response.hovered {
+        style.frame_color(hover_color(style.frame_color))
+    } else {
+        style
+    };
+
+    frame::show(
+        ui,
+        false,
+        style,
+        None,
+        style.enabled.then(|| ui.current_id()),
+        true,
+        move |ui| ui.text(text),
+    );
+}
@@ -8,7 +8,10 @@ use std::f64::consts::{FRAC_PI_2, PI};
 
 pub struct DrawApi<'a> {
     context: &'a Context,
-    pub(crate) boxes: Vec<(Id, BoundingBox)>,
+    pub(crate) boxes: Vec<(Id, BoundingBox, u32)>,
+    pub(crate) focus_ring: Option<Id>,
+    clip_rects: Vec<BoundingBox>,
+    layer: u32,
     pub pass: i32,
     pub scale: f32,
     tint: Vec4,
@@ -19,12 +22,20 @@ impl<'a> DrawApi<'a> {
         DrawApi {
             context,
             boxes: Vec::new(),
+            focus_ring: None,
+            clip_rects: Vec::new(),
+            layer: 0,
             pass: 1,
             scale: 1.0,
             tint: Vec4::ONE,
         }
     }
 
+    #[inline]
+    pub(crate) fn context(&self) -> &'a Context {
+        self.context
+    }
+
     #[inline]
     pub fn calc_text_size(&self, text: &str, text_height: f32, max_width: f32, font: Font) -> Vec2 {
         self.context.set_font_size(text_height as _);
@@ -253,6 +264,55 @@ impl<'a> DrawApi<'a> {
         self.context.stroke().unwrap();
     }
 
+    /// Restricts all drawing to the given rectangle, intersected with any
+    /// clip that is already active, until the matching `pop_clip`.
+    #[inline]
+    pub fn push_clip(&mut self, pos: Vec2, size: Vec2) {
+        let rect = BoundingBox::new(pos, size);
+        let rect = match self.clip_rects.last() {
+            Some(current) => rect
+                .clip(current)
+                .unwrap_or(BoundingBox::new(pos, Vec2::ZERO)),
+            None => rect,
+        };
+
+        self.context.save().unwrap();
+        self.context.new_path();
+        self.context.rectangle(
+            rect.top_left.x as _,
+            -rect.top_left.y as _,
+            rect.size.x as _,
+            rect.size.y as _,
+        );
+        self.context.clip();
+
+        self.clip_rects.push(rect);
+    }
+
+    #[inline]
+    pub fn pop_clip(&mut self) {
+        if self.clip_rects.pop().is_some() {
+            self.context.restore().unwrap();
+        }
+    }
+
+    #[inline]
+    pub(crate) fn clip_rect(&self) -> Option<BoundingBox> {
+        self.clip_rects.last().copied()
+    }
+
+    #[inline]
+    pub(crate) fn push_box(&mut self, id: Id, bb: BoundingBox) {
+        let bb = match self.clip_rects.last() {
+            Some(clip) => bb.clip(clip),
+            None => Some(bb),
+        };
+
+        if let Some(bb) = bb {
+            self.boxes.push((id, bb, self.layer));
+        }
+    }
+
     #[inline]
     pub fn set_tint(&mut self, tint: Vec4) {
         if self.pass == 0 {
@@ -267,6 +327,13 @@ impl<'a> DrawApi<'a> {
         self.tint = tint;
     }
 
+    /// Boxes pushed on a higher layer hide the boxes of all lower layers
+    /// beneath them from hit testing.
+    #[inline]
+    pub(crate) fn set_layer(&mut self, layer: u32) {
+        self.layer = layer;
+    }
+
     #[inline]
     pub(crate) fn set_pass(&mut self, pass: i32) {
         self.pass = pass;
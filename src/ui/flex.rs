@@ -0,0 +1,356 @@
+use crate::{
+    math::{vec2, Vec2},
+    ui::{
+        frame,
+        grid::container_style,
+        ui::{Element, UpdateWithMaxWidth},
+        Ui,
+    },
+};
+use std::{cell::Cell, rc::Rc};
+
+/// An area with its own flex weights and size limits.
+///
+/// Extra width is shared between the flexible elements of a line by weight, on top
+/// of their own width. Extra height is shared in the same way between the lines
+/// that hold an element with a vertical weight, like `Ui::vertical_spring`.
+#[must_use = "You should call .show()"]
+pub struct Flex {
+    weight: Vec2,
+    basis: Option<f32>,
+    min_size: Vec2,
+    max_size: Vec2,
+}
+
+impl Default for Flex {
+    fn default() -> Flex {
+        Flex {
+            weight: vec2(1.0, 0.0),
+            basis: None,
+            min_size: Vec2::ZERO,
+            max_size: Vec2::INFINITY,
+        }
+    }
+}
+
+impl Flex {
+    #[inline]
+    pub fn new() -> Flex {
+        Flex::default()
+    }
+
+    /// Share of the extra width, 0 keeps the area at its own width.
+    #[inline]
+    pub fn weight(mut self, weight: f32) -> Flex {
+        self.weight.x = weight;
+        self
+    }
+
+    /// Share of the extra height, 0 keeps the area at its own height.
+    #[inline]
+    pub fn vertical_weight(mut self, weight: f32) -> Flex {
+        self.weight.y = weight;
+        self
+    }
+
+    /// Width to grow from instead of the width of the content, so that areas with a
+    /// basis of 0 split the line by weight alone. Content wider than the result overflows.
+    #[inline]
+    pub fn basis(mut self, width: f32) -> Flex {
+        self.basis = Some(width);
+        self
+    }
+
+    #[inline]
+    pub fn min_width(mut self, width: f32) -> Flex {
+        self.min_size.x = width;
+        self
+    }
+
+    #[inline]
+    pub fn max_width(mut self, width: f32) -> Flex {
+        self.max_size.x = width;
+        self
+    }
+
+    #[inline]
+    pub fn min_height(mut self, height: f32) -> Flex {
+        self.min_size.y = height;
+        self
+    }
+
+    #[inline]
+    pub fn max_height(mut self, height: f32) -> Flex {
+        self.max_size.y = height;
+        self
+    }
+
+    pub fn show(self, ui: &mut Ui, func: impl FnOnce(&mut Ui)) {
+        let style = ui.style;
+        let parent_id = ui.parent_id.with_child(ui.current_id());
+
+        let mut element = frame::build(
+            ui,
+            true,
+            container_style(style),
+            None,
+            None,
+            parent_id,
+            true,
+            |ui| {
+                ui.style = style;
+                func(ui);
+            },
+        );
+
+        let content_box = element.content_box.get();
+        let mut size = content_box.max(self.min_size);
+
+        if let Some(basis) = self.basis {
+            size.x = basis.max(self.min_size.x);
+        }
+
+        // Content larger than the max size overflows the area
+        let size = size.min(self.max_size);
+
+        element.content_box.set(size);
+
+        if let Some(update_with_max_width) = element.update_with_max_width.as_ref() {
+            update_with_max_width(size - content_box);
+        }
+
+        element.flex_x = self.weight.x > 0.0;
+        element.weight = vec2(self.weight.x, self.weight.y.max(element.weight.y));
+        element.max_size = self.max_size;
+
+        ui.current_line.push(element);
+    }
+}
+
+/// A flexible element, along with how much it has grown so far.
+pub(crate) struct FlexItem {
+    content_box: Rc<Cell<Vec2>>,
+    update: Option<Rc<UpdateWithMaxWidth>>,
+    weight: f32,
+    max_extra: f32,
+    axis: Vec2,
+    applied: Cell<f32>,
+}
+
+impl FlexItem {
+    /// `axis` is `vec2(1.0, 0.0)` for growing in width and `vec2(0.0, 1.0)` for height.
+    pub(crate) fn new(element: &Element, axis: Vec2) -> FlexItem {
+        let size = element.content_box.get().dot(axis);
+
+        FlexItem {
+            content_box: element.content_box.clone(),
+            update: element.update_with_max_width.clone(),
+            weight: element.weight.dot(axis),
+            max_extra: (element.max_size.dot(axis) - size).max(0.0),
+            axis,
+            applied: Cell::new(0.0),
+        }
+    }
+
+    fn set_extra(&self, extra: f32) -> f32 {
+        let delta = extra - self.applied.get();
+
+        if delta != 0.0 {
+            self.applied.set(extra);
+            self.content_box
+                .set(self.content_box.get() + delta * self.axis);
+
+            if let Some(update) = self.update.as_ref() {
+                update(delta * self.axis);
+            }
+        }
+
+        delta
+    }
+}
+
+/// The flexible elements of a line, sharing the width the line doesn't use.
+pub(crate) struct FlexRow {
+    items: Vec<FlexItem>,
+    line_size: Rc<Cell<Vec2>>,
+    free: Cell<f32>,
+}
+
+impl FlexRow {
+    pub(crate) fn new(items: Vec<FlexItem>, line_size: Rc<Cell<Vec2>>) -> FlexRow {
+        FlexRow {
+            items,
+            line_size,
+            free: Cell::new(0.0),
+        }
+    }
+
+    pub(crate) fn grow(&self, extra: f32) {
+        self.free.set(self.free.get() + extra);
+
+        let extras = distribute(&self.items, self.free.get());
+        let delta: f32 = self
+            .items
+            .iter()
+            .zip(extras)
+            .map(|(item, extra)| item.set_extra(extra))
+            .sum();
+
+        self.line_size.set(self.line_size.get() + vec2(delta, 0.0));
+    }
+}
+
+/// A line of a frame that grows in height, along with its elements that grow with it.
+struct FlexLine {
+    weight: f32,
+    line_size: Rc<Cell<Vec2>>,
+    items: Vec<FlexItem>,
+    applied: Cell<f32>,
+}
+
+/// The lines of a frame that share the height the frame doesn't use.
+pub(crate) struct FlexColumn {
+    lines: Vec<FlexLine>,
+    free: Cell<f32>,
+}
+
+impl FlexColumn {
+    pub(crate) fn new() -> FlexColumn {
+        FlexColumn {
+            lines: Vec::new(),
+            free: Cell::new(0.0),
+        }
+    }
+
+    pub(crate) fn push(&mut self, weight: f32, line_size: Rc<Cell<Vec2>>, items: Vec<FlexItem>) {
+        self.lines.push(FlexLine {
+            weight,
+            line_size,
+            items,
+            applied: Cell::new(0.0),
+        });
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.lines.is_empty()
+    }
+
+    pub(crate) fn grow(&self, extra: f32) {
+        self.free.set(self.free.get() + extra);
+
+        let weights: f32 = self.lines.iter().map(|line| line.weight).sum();
+
+        for line in &self.lines {
+            let share = self.free.get() * line.weight / weights;
+
+            line.line_size
+                .set(line.line_size.get() + vec2(0.0, share - line.applied.get()));
+            line.applied.set(share);
+
+            for item in &line.items {
+                item.set_extra(share.min(item.max_extra));
+            }
+        }
+    }
+}
+
+/// Splits `free` by weight, an item that reaches its max size gives the rest
+/// of its share to the others.
+fn distribute(items: &[FlexItem], free: f32) -> Vec<f32> {
+    let mut extras = vec![0.0; items.len()];
+    let mut growing = (0..items.len())
+        .filter(|index| items[*index].weight > 0.0)
+        .collect::<Vec<_>>();
+    let mut remaining = free.max(0.0);
+
+    while !growing.is_empty() {
+        let weights: f32 = growing.iter().map(|index| items[*index].weight).sum();
+        let share = |index: usize| remaining * items[index].weight / weights;
+
+        let full = growing
+            .iter()
+            .copied()
+            .filter(|index| share(*index) > items[*index].max_extra)
+            .collect::<Vec<_>>();
+
+        if full.is_empty() {
+            for index in growing {
+                extras[index] = share(index);
+            }
+            break;
+        }
+
+        for index in full {
+            extras[index] = items[index].max_extra;
+            remaining -= items[index].max_extra;
+            growing.retain(|growing| *growing != index);
+        }
+    }
+
+    extras
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn item(weight: f32, max_extra: f32) -> FlexItem {
+        FlexItem {
+            content_box: Rc::new(Cell::new(Vec2::ZERO)),
+            update: None,
+            weight,
+            max_extra,
+            axis: vec2(1.0, 0.0),
+            applied: Cell::new(0.0),
+        }
+    }
+
+    #[test]
+    fn distribute_by_weight() {
+        let items = [item(1.0, f32::INFINITY), item(1.0, f32::INFINITY)];
+        assert_eq!(distribute(&items, 300.0), [150.0, 150.0]);
+
+        let items = [item(1.0, f32::INFINITY), item(0.0, f32::INFINITY)];
+        assert_eq!(distribute(&items, 300.0), [300.0, 0.0]);
+    }
+
+    #[test]
+    fn distribute_sidebar_and_content() {
+        // A sidebar with a quarter of the width, the content with the rest
+        let items = [item(1.0, f32::INFINITY), item(3.0, f32::INFINITY)];
+        assert_eq!(distribute(&items, 800.0), [200.0, 600.0]);
+    }
+
+    #[test]
+    fn distribute_past_max_size() {
+        let items = [item(1.0, 50.0), item(1.0, f32::INFINITY)];
+        assert_eq!(distribute(&items, 300.0), [50.0, 250.0]);
+
+        // Space nobody can take is left over
+        let items = [item(1.0, 10.0), item(1.0, 20.0)];
+        assert_eq!(distribute(&items, 100.0), [10.0, 20.0]);
+    }
+
+    #[test]
+    fn distribute_nothing() {
+        let items = [item(1.0, f32::INFINITY), item(3.0, f32::INFINITY)];
+        assert_eq!(distribute(&items, -50.0), [0.0, 0.0]);
+        assert!(distribute(&[], 100.0).is_empty());
+    }
+
+    #[test]
+    fn row_grows_in_steps() {
+        let line_size = Rc::new(Cell::new(Vec2::ZERO));
+        let row = FlexRow::new(
+            vec![item(1.0, f32::INFINITY), item(3.0, f32::INFINITY)],
+            line_size.clone(),
+        );
+
+        row.grow(400.0);
+        row.grow(400.0);
+
+        assert_eq!(row.items[0].content_box.get().x, 200.0);
+        assert_eq!(row.items[1].content_box.get().x, 600.0);
+        assert_eq!(line_size.get().x, 800.0);
+    }
+}
@@ -0,0 +1,472 @@
+use crate::{
+    math::{vec2, Vec2},
+    ui::{
+        bounding_box::BoundingBox,
+        collapsing::draw_arrow,
+        color::held_color,
+        frame,
+        id::Id,
+        memory::Persist,
+        popup::{self, Placement},
+        Spacing, Ui,
+    },
+    window::CursorStyle,
+};
+use std::{borrow::Cow, fmt, hash::Hash, str::FromStr};
+
+const HANDLE_WIDTH: f32 = 6.0;
+
+// Edges first so the corners win where they overlap
+const HANDLES: [(i32, i32); 8] = [
+    (-1, 0),
+    (1, 0),
+    (0, -1),
+    (0, 1),
+    (-1, -1),
+    (1, -1),
+    (-1, 1),
+    (1, 1),
+];
+
+/// A window floating above the rest of the ui, with a title bar to drag it by.
+///
+/// Clicking a window brings it to the front. Its position, size and whether it
+/// is collapsed are kept in `Memory` and included in `Memory::serialize`.
+#[must_use = "You should call .show()"]
+pub struct FloatingWindow<'a> {
+    id: Id,
+    title: Cow<'a, str>,
+    default_pos: Vec2,
+    default_size: Option<Vec2>,
+    min_size: Vec2,
+    resizable: bool,
+    collapsible: bool,
+    closable: bool,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+struct WindowState {
+    pos: Option<Vec2>,
+    size: Option<Vec2>,
+    collapsed: bool,
+}
+
+impl<'a> FloatingWindow<'a> {
+    pub fn new(id_source: impl Hash, title: impl Into<Cow<'a, str>>) -> FloatingWindow<'a> {
+        FloatingWindow {
+            id: Id::new(id_source),
+            title: title.into(),
+            default_pos: vec2(32.0, 32.0),
+            default_size: None,
+            min_size: vec2(64.0, 0.0),
+            resizable: true,
+            collapsible: true,
+            closable: true,
+        }
+    }
+
+    /// Offset of the top left corner from the top left of the screen, until the
+    /// window is moved.
+    #[inline]
+    pub fn default_pos(mut self, pos: Vec2) -> FloatingWindow<'a> {
+        self.default_pos = pos;
+        self
+    }
+
+    /// Size until the window is resized, defaults to the size of the content.
+    #[inline]
+    pub fn default_size(mut self, size: Vec2) -> FloatingWindow<'a> {
+        self.default_size = Some(size);
+        self
+    }
+
+    #[inline]
+    pub fn min_size(mut self, size: Vec2) -> FloatingWindow<'a> {
+        self.min_size = size;
+        self
+    }
+
+    #[inline]
+    pub fn resizable(mut self, resizable: bool) -> FloatingWindow<'a> {
+        self.resizable = resizable;
+        self
+    }
+
+    #[inline]
+    pub fn collapsible(mut self, collapsible: bool) -> FloatingWindow<'a> {
+        self.collapsible = collapsible;
+        self
+    }
+
+    #[inline]
+    pub fn closable(mut self, closable: bool) -> FloatingWindow<'a> {
+        self.closable = closable;
+        self
+    }
+
+    /// Returns false once the close button is pressed.
+    pub fn show(self, ui: &mut Ui<'a, '_, '_>, func: impl FnOnce(&mut Ui)) -> bool {
+        let style = ui.style;
+        let id = ui.parent_id.with_child(self.id);
+        let title_id = id.with_child(Id::new("title"));
+
+        let mut state = *ui.memory.get_persisted_or_default::<WindowState>(id);
+        let mut changed = false;
+
+        let to_front = ui.response_for(id).hovered && ui.state.mouse_just_pressed;
+        let order = &mut ui.state.window_order;
+
+        if to_front {
+            order.retain(|window| *window != id);
+        }
+
+        if !order.contains(&id) {
+            order.push(id);
+        }
+
+        let mut pos = state.pos.unwrap_or(self.default_pos);
+
+        let title_bar = ui.response_for(title_id);
+
+        if title_bar.dragged {
+            pos += vec2(title_bar.drag_delta.x, -title_bar.drag_delta.y);
+            ui.set_cursor(CursorStyle::ClosedHand);
+            changed = true;
+        }
+
+        // The size is only known once the content is laid out, so the
+        // overlay is adjusted after it is built
+        let index = ui.overlays.len();
+        let collapsed = state.collapsed;
+        let foreground = style.text_color;
+        let text_height = style.text_height;
+        let title = self.title.into_owned();
+
+        let mut toggle_collapsed = false;
+        let mut close = false;
+
+        let window_style = style.margin(Spacing::ZERO).padding(Spacing::ZERO);
+        let title_style = style.frame_color(held_color(style.frame_color));
+
+        popup::show(
+            ui,
+            id,
+            Placement::Floating(vec2(pos.x, -pos.y)),
+            true,
+            window_style,
+            |ui| {
+                let title_bar = frame::build(
+                    ui,
+                    false,
+                    title_style,
+                    None,
+                    Some(title_id),
+                    title_id,
+                    true,
+                    |ui| {
+                        if self.collapsible {
+                            toggle_collapsed = ui
+                                .interactable_canvas(
+                                    vec2(text_height, text_height),
+                                    move |draw, cursor, size| {
+                                        draw_arrow(draw, cursor, size, !collapsed, foreground);
+                                    },
+                                )
+                                .pressed;
+                        }
+
+                        ui.colored_text(title, foreground);
+                        ui.horizontal_spring();
+
+                        if self.closable {
+                            close = ui
+                                .interactable_canvas(
+                                    vec2(text_height, text_height),
+                                    move |draw, cursor, size| {
+                                        let extent = size.x.min(size.y) * 0.2;
+                                        let center = cursor + vec2(size.x, -size.y) / 2.0;
+
+                                        draw.line(
+                                            center - vec2(extent, extent),
+                                            center + vec2(extent, extent),
+                                            extent / 2.0,
+                                            foreground,
+                                        );
+                                        draw.line(
+                                            center - vec2(extent, -extent),
+                                            center + vec2(extent, -extent),
+                                            extent / 2.0,
+                                            foreground,
+                                        );
+                                    },
+                                )
+                                .pressed;
+                        }
+                    },
+                );
+
+                ui.current_line.push(title_bar);
+
+                if !collapsed {
+                    ui.next_line();
+
+                    let body = frame::build(
+                        ui,
+                        true,
+                        style,
+                        None,
+                        None,
+                        id.with_child(Id::new("body")),
+                        true,
+                        func,
+                    );
+
+                    ui.current_line.push(body);
+                }
+            },
+        );
+
+        let content_box = ui.overlays[index].element.content_box.get();
+
+        let mut size = state.size.or(self.default_size).unwrap_or(content_box);
+
+        if collapsed {
+            size.y = content_box.y;
+        }
+
+        let mut resized = None;
+
+        if self.resizable && !collapsed {
+            for (h, v) in HANDLES {
+                let response = ui.response_for(handle_id(id, h, v));
+
+                if !response.hovered && !response.dragged {
+                    continue;
+                }
+
+                resized = Some(match (h, v) {
+                    (0, _) => CursorStyle::ResizeUpDown,
+                    (_, 0) => CursorStyle::ResizeLeftRight,
+                    _ => CursorStyle::ResizeAll,
+                });
+
+                if !response.dragged {
+                    continue;
+                }
+
+                // Down and to the right are positive, like the position
+                let delta = vec2(response.drag_delta.x, -response.drag_delta.y);
+
+                (pos, size) = resize(pos, size, h, v, delta, self.min_size);
+                state.size = Some(size);
+                changed = true;
+            }
+        }
+
+        if let Some(cursor) = resized {
+            ui.set_cursor(cursor);
+        }
+
+        let element = &mut ui.overlays[index].element;
+
+        if !collapsed {
+            size = size.max(self.min_size);
+        }
+
+        // Content larger than the window is clipped
+        let extra_size = (size - content_box).max(Vec2::ZERO);
+
+        if extra_size != Vec2::ZERO {
+            if let Some(update_with_max_width) = element.update_with_max_width.as_ref() {
+                update_with_max_width(extra_size);
+            }
+        }
+
+        element.content_box.set(size);
+
+        if let Some(render) = element.render.take() {
+            let resizable = self.resizable && !collapsed;
+            let border_width = style.border_width;
+
+            element.render = Some(Box::new(move |draw, cursor, size| {
+                draw.push_clip(
+                    cursor - vec2(border_width, -border_width),
+                    size + 2.0 * vec2(border_width, border_width),
+                );
+                render(draw, cursor, size);
+                draw.pop_clip();
+
+                if resizable && draw.pass() == 1 {
+                    for (h, v) in HANDLES {
+                        draw.push_box(handle_id(id, h, v), handle_box(cursor, size, h, v));
+                    }
+                }
+            }));
+        }
+
+        // Keep part of the title bar on the screen to drag the window back by
+        let pos = pos
+            .min(ui.state.screen_size - vec2(HANDLE_WIDTH * 4.0, text_height))
+            .max(vec2(HANDLE_WIDTH * 4.0 - size.x, 0.0));
+
+        ui.overlays[index].placement = Placement::Floating(vec2(pos.x, -pos.y));
+
+        if toggle_collapsed {
+            state.collapsed = !state.collapsed;
+            changed = true;
+        }
+
+        if changed {
+            state.pos = Some(pos);
+            ui.memory.insert(id, state);
+        }
+
+        !close
+    }
+}
+
+#[inline]
+fn handle_id(id: Id, h: i32, v: i32) -> Id {
+    id.with_child(Id::from_vec2(vec2(h as f32, v as f32)))
+}
+
+/// Moves the edges of handle `h`, `v` by `delta`, keeping the window at least
+/// `min_size`. The left and top handles move `pos` so the opposite edges stay put.
+fn resize(pos: Vec2, size: Vec2, h: i32, v: i32, delta: Vec2, min_size: Vec2) -> (Vec2, Vec2) {
+    let new_size = (size + vec2(h as f32, v as f32) * delta).max(min_size);
+    let mut pos = pos;
+
+    if h < 0 {
+        pos.x += size.x - new_size.x;
+    }
+
+    if v < 0 {
+        pos.y += size.y - new_size.y;
+    }
+
+    (pos, new_size)
+}
+
+/// The strip along the edge or the square on the corner the handle covers,
+/// centered on the border.
+fn handle_box(cursor: Vec2, size: Vec2, h: i32, v: i32) -> BoundingBox {
+    let (left, width) = match h {
+        -1 => (cursor.x - HANDLE_WIDTH / 2.0, HANDLE_WIDTH),
+        1 => (cursor.x + size.x - HANDLE_WIDTH / 2.0, HANDLE_WIDTH),
+        _ => (cursor.x + HANDLE_WIDTH / 2.0, size.x - HANDLE_WIDTH),
+    };
+
+    let (top, height) = match v {
+        -1 => (cursor.y + HANDLE_WIDTH / 2.0, HANDLE_WIDTH),
+        1 => (cursor.y - size.y + HANDLE_WIDTH / 2.0, HANDLE_WIDTH),
+        _ => (cursor.y - HANDLE_WIDTH / 2.0, size.y - HANDLE_WIDTH),
+    };
+
+    BoundingBox::new(vec2(left, top), vec2(width, height))
+}
+
+impl Persist for WindowState {
+    const TAG: &'static str = "window";
+}
+
+impl fmt::Display for WindowState {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for value in [self.pos, self.size] {
+            match value {
+                Some(value) => write!(f, "{} {} ", value.x, value.y)?,
+                None => write!(f, "- - ")?,
+            }
+        }
+
+        write!(f, "{}", self.collapsed)
+    }
+}
+
+impl FromStr for WindowState {
+    type Err = ();
+
+    fn from_str(text: &str) -> Result<WindowState, ()> {
+        let parts = text.split_whitespace().collect::<Vec<_>>();
+
+        let [pos_x, pos_y, size_x, size_y, collapsed] = parts[..] else {
+            return Err(());
+        };
+
+        let pair = |x: &str, y: &str| -> Result<Option<Vec2>, ()> {
+            match (x, y) {
+                ("-", "-") => Ok(None),
+                _ => Ok(Some(vec2(
+                    x.parse().map_err(|_| ())?,
+                    y.parse().map_err(|_| ())?,
+                ))),
+            }
+        };
+
+        Ok(WindowState {
+            pos: pair(pos_x, pos_y)?,
+            size: pair(size_x, size_y)?,
+            collapsed: collapsed.parse().map_err(|_| ())?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn window_state_round_trip() {
+        for state in [
+            WindowState::default(),
+            WindowState {
+                pos: Some(vec2(10.5, -3.0)),
+                size: None,
+                collapsed: true,
+            },
+            WindowState {
+                pos: Some(vec2(0.0, 40.0)),
+                size: Some(vec2(320.0, 200.25)),
+                collapsed: false,
+            },
+        ] {
+            assert_eq!(state.to_string().parse::<WindowState>(), Ok(state));
+        }
+    }
+
+    #[test]
+    fn left_and_top_handles_move_the_window() {
+        let pos = vec2(10.0, 20.0);
+        let size = vec2(200.0, 100.0);
+        let min_size = vec2(64.0, 32.0);
+
+        assert_eq!(
+            resize(pos, size, -1, 0, vec2(30.0, 5.0), min_size),
+            (vec2(40.0, 20.0), vec2(170.0, 100.0))
+        );
+        assert_eq!(
+            resize(pos, size, 0, -1, vec2(5.0, -30.0), min_size),
+            (vec2(10.0, -10.0), vec2(200.0, 130.0))
+        );
+        assert_eq!(
+            resize(pos, size, 1, 1, vec2(30.0, 10.0), min_size),
+            (pos, vec2(230.0, 110.0))
+        );
+    }
+
+    #[test]
+    fn resize_stops_at_min_size() {
+        let pos = vec2(10.0, 20.0);
+        let size = vec2(200.0, 100.0);
+        let min_size = vec2(64.0, 32.0);
+
+        // The right and bottom edges stay where they were
+        assert_eq!(
+            resize(pos, size, -1, -1, vec2(500.0, 500.0), min_size),
+            (vec2(146.0, 88.0), min_size)
+        );
+        assert_eq!(
+            resize(pos, size, 1, 1, vec2(-500.0, -500.0), min_size),
+            (pos, min_size)
+        );
+    }
+}
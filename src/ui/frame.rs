@@ -1,7 +1,13 @@
 use crate::{
     math::{vec2, vec4, Vec2},
     ui::{
-        bounding_box::BoundingBox, debug, draw_api::DrawApi, id::Id, spacing, ui::Element, Align, Style, Ui, VertAlign,
+        bounding_box::BoundingBox,
+        debug,
+        draw_api::DrawApi,
+        id::Id,
+        spacing,
+        ui::{Element, FLEX_WEIGHT},
+        Align, Style, Ui, VertAlign,
     },
 };
 use std::{cell::Cell, rc::Rc};
@@ -23,20 +29,58 @@ pub(crate) fn show(
     expand_from_below: bool,
     func: impl FnOnce(&mut Ui),
 ) {
-    let current_id = ui.current_id();
+    let parent_id = ui.parent_id.with_child(ui.current_id());
+    let element = build(
+        ui,
+        hide_frame,
+        style,
+        fixed_size,
+        id,
+        parent_id,
+        expand_from_below,
+        func,
+    );
 
+    ui.current_line.push(element);
+}
+
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn build<'a>(
+    ui: &mut Ui<'a, '_, '_>,
+    hide_frame: bool,
+    style: Style,
+    fixed_size: Option<Vec2>,
+    id: Option<Id>,
+    parent_id: Id,
+    expand_from_below: bool,
+    func: impl FnOnce(&mut Ui),
+) -> Element<'a> {
     let mut child_ui = Ui {
         draw: ui.draw,
+        window: ui.window,
         responses: ui.responses,
+        state: ui.state,
+        memory: ui.memory,
+        overlays: ui.overlays,
         style,
         current_line: Vec::new(),
         lines: Vec::new(),
-        parent_id: ui.parent_id.with_child(current_id),
+        parent_id,
+        id_origin: (0, 0),
+        next_id: None,
+        panels: Vec::new(),
     };
     func(&mut child_ui);
 
     let (lines, mut line_sizes, mut total_size, flex_children) = child_ui.layout();
 
+    let found_flex = lines.iter().flatten().any(|element| element.flex_x);
+    let weight_y = lines
+        .iter()
+        .flatten()
+        .map(|element| element.weight.y)
+        .fold(0.0, f32::max);
+
     let border_extra = if hide_frame {
         Vec2::ZERO
     } else {
@@ -51,162 +95,206 @@ pub(crate) fn show(
         }
         if fixed_size.y > 0.0 {
             if fixed_size.y > total_size.y {
-                let last_line = line_sizes.last_mut().unwrap();
+                let extra_height = fixed_size.y - total_size.y;
+
+                // Vertical springs take the extra height, otherwise the last line does
+                if weight_y > 0.0 {
+                    for flex_child in &flex_children {
+                        flex_child(vec2(0.0, extra_height));
+                    }
+                } else {
+                    let last_line = line_sizes.last_mut().unwrap();
 
-                last_line.set(last_line.get() + vec2(0.0, fixed_size.y - total_size.y));
+                    last_line.set(last_line.get() + vec2(0.0, extra_height));
+                }
             }
 
             total_size.y = fixed_size.y;
         }
     }
 
-    let found_flex = !flex_children.is_empty();
-
-    ui.current_line.push(Element {
+    Element {
         content_box: Rc::new(Cell::new(total_size)),
         id,
         border_extra,
         flex_x: found_flex && expand_from_below,
         flex_y: false,
+        weight: if expand_from_below {
+            vec2(FLEX_WEIGHT.x, weight_y)
+        } else {
+            FLEX_WEIGHT
+        },
+        max_size: Vec2::INFINITY,
         style,
         render: Some(Box::new(move |draw, parent_cursor, total_size| {
-            let pass = draw.pass();
+            if !hide_frame {
+                render_frame(draw, &style, parent_cursor, total_size);
+            }
 
-            {
-                if pass == 0 {
-                    draw.set_tint_internal(style.shadow_color);
-                    draw.set_scale(style.shadow_scale);
+            if draw.pass() == 1 {
+                render_lines(
+                    draw,
+                    parent_cursor,
+                    total_size,
+                    border_extra,
+                    &style,
+                    &lines,
+                    &line_sizes,
+                );
+            }
+        })),
+        update_with_max_width: if !flex_children.is_empty() && expand_from_below {
+            Some(Rc::new(move |extra_size: Vec2| {
+                for flex_child in &flex_children {
+                    flex_child(extra_size);
                 }
+            }))
+        } else {
+            None
+        },
+    }
+}
 
-                if !hide_frame && (pass == 1 || (pass == 0 && style.shadow_dir.is_some())) {
-                    draw_frame(
-                        draw,
-                        &style,
-                        parent_cursor - vec2(style.padding.left, -style.padding.top),
-                        total_size + vec2(style.padding.width(), style.padding.height()),
-                    );
+pub(crate) fn render_frame(
+    draw: &mut DrawApi,
+    style: &Style,
+    parent_cursor: Vec2,
+    total_size: Vec2,
+) {
+    let pass = draw.pass();
+
+    if pass == 0 {
+        draw.set_tint_internal(style.shadow_color);
+        draw.set_scale(style.shadow_scale);
+    }
+
+    if pass == 1 || (pass == 0 && style.shadow_dir.is_some()) {
+        draw_frame(
+            draw,
+            style,
+            parent_cursor - vec2(style.padding.left, -style.padding.top),
+            total_size + vec2(style.padding.width(), style.padding.height()),
+        );
+    }
+
+    if pass == 0 {
+        draw.set_tint_internal(vec4(1.0, 1.0, 1.0, 1.0));
+        draw.set_scale(1.0);
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn render_lines(
+    draw: &mut DrawApi,
+    parent_cursor: Vec2,
+    total_size: Vec2,
+    border_extra: Vec2,
+    style: &Style,
+    lines: &[Vec<Element>],
+    line_sizes: &[Rc<Cell<Vec2>>],
+) {
+    for pass in 0..2 {
+        draw.set_pass(pass);
+
+        let mut cursor_y = -border_extra.y;
+
+        for (line, line_size) in lines.iter().zip(line_sizes.iter()) {
+            let mut cursor_x = match style.align.unwrap_or(Align::Left) {
+                Align::Right => total_size.x - line_size.get().x - border_extra.x,
+                Align::Left => border_extra.x,
+                Align::Center => total_size.x / 2.0 - line_size.get().x / 2.0,
+            };
+
+            for element in line {
+                let bounding_box = spacing::bounding_box(
+                    element.content_box.get(),
+                    element.style.margin,
+                    element.style.padding,
+                );
+
+                let content_height = line_size.get().y
+                    - element.style.margin.height()
+                    - element.style.padding.height();
+
+                let align_y = if element.flex_y {
+                    0.0
+                } else {
+                    match element.style.vert_align {
+                        VertAlign::Top => 0.0,
+                        VertAlign::Bottom => element.content_box.get().y - content_height,
+                        VertAlign::Center => {
+                            element.content_box.get().y / 2.0 - content_height / 2.0
+                        }
+                    }
+                };
+
+                let cursor = vec2(cursor_x, cursor_y + align_y);
+
+                if element.style.debug && pass == 1 {
+                    debug::draw_content_boxes(draw, parent_cursor + cursor, element, content_height)
                 }
 
+                let cursor = cursor
+                    + vec2(
+                        element.style.padding.left + element.style.margin.left,
+                        -element.style.padding.top - element.style.margin.top,
+                    )
+                    + if pass == 0 {
+                        element.style.shadow_dir.unwrap_or(Vec2::ZERO)
+                    } else {
+                        Vec2::ZERO
+                    };
+
                 if pass == 0 {
-                    draw.set_tint_internal(vec4(1.0, 1.0, 1.0, 1.0));
-                    draw.set_scale(1.0);
+                    draw.set_tint_internal(element.style.shadow_color);
+                    draw.set_scale(element.style.shadow_scale);
                 }
-            }
 
-            if pass == 1 {
-                for pass in 0..2 {
-                    draw.set_pass(pass);
-
-                    let mut cursor_y = -border_extra.y;
-
-                    for (line, line_size) in lines.iter().zip(line_sizes.iter()) {
-                        let mut cursor_x = match style.align.unwrap_or(Align::Left) {
-                            Align::Right => total_size.x - line_size.get().x - border_extra.x,
-                            Align::Left => border_extra.x,
-                            Align::Center => total_size.x / 2.0 - line_size.get().x / 2.0,
-                        };
-
-                        for element in line {
-                            let bounding_box = spacing::bounding_box(
-                                element.content_box.get(),
-                                element.style.margin,
-                                element.style.padding,
-                            );
-
-                            let content_height = line_size.get().y
-                                - element.style.margin.height()
-                                - element.style.padding.height();
-
-                            let align_y = if element.flex_y {
-                                0.0
-                            } else {
-                                match element.style.vert_align {
-                                    VertAlign::Top => 0.0,
-                                    VertAlign::Bottom => {
-                                        element.content_box.get().y - content_height
-                                    }
-                                    VertAlign::Center => {
-                                        element.content_box.get().y / 2.0 - content_height / 2.0
-                                    }
-                                }
-                            };
-
-                            let cursor = vec2(cursor_x, cursor_y + align_y);
-
-                            if element.style.debug && pass == 1 {
-                                debug::draw_content_boxes(
-                                    draw,
-                                    parent_cursor + cursor,
-                                    element,
-                                    content_height,
-                                )
-                            }
-
-                            let cursor = cursor
-                                + vec2(
-                                    element.style.padding.left + element.style.margin.left,
-                                    -element.style.padding.top - element.style.margin.top,
-                                )
-                                + if pass == 0 {
-                                    element.style.shadow_dir.unwrap_or(Vec2::ZERO)
-                                } else {
-                                    Vec2::ZERO
-                                };
-
-                            if pass == 0 {
-                                draw.set_tint_internal(element.style.shadow_color);
-                                draw.set_scale(element.style.shadow_scale);
-                            }
-
-                            if pass == 1 || (pass == 0 && element.style.shadow_dir.is_some()) {
-                                let element_cursor = parent_cursor + cursor;
-
-                                let element_size = vec2(
-                                    element.content_box.get().x,
-                                    if element.flex_y {
-                                        content_height
-                                    } else {
-                                        element.content_box.get().y
-                                    },
-                                );
-
-                                if pass == 1 {
-                                    if let Some(id) = element.id {
-                                        draw.boxes.push((
-                                            id,
-                                            BoundingBox::new(element_cursor, element_size),
-                                        ));
-                                    }
-                                }
-
-                                if let Some(render) = element.render.as_ref() {
-                                    render(draw, element_cursor, element_size);
-                                }
-                            }
-
-                            if pass == 0 {
-                                draw.set_tint_internal(vec4(1.0, 1.0, 1.0, 1.0));
-                                draw.set_scale(1.0);
-                            }
-
-                            cursor_x += bounding_box.width();
+                if pass == 1 || (pass == 0 && element.style.shadow_dir.is_some()) {
+                    let element_cursor = parent_cursor + cursor;
+
+                    let element_size = vec2(
+                        element.content_box.get().x,
+                        if element.flex_y {
+                            content_height
+                        } else {
+                            element.content_box.get().y
+                        },
+                    );
+
+                    if pass == 1 {
+                        if let Some(id) = element.id {
+                            draw.push_box(id, BoundingBox::new(element_cursor, element_size));
                         }
+                    }
 
-                        cursor_y -= line_size.get().y;
+                    if let Some(render) = element.render.as_ref() {
+                        render(draw, element_cursor, element_size);
+                    }
+
+                    if pass == 1 && element.id.is_some() && element.id == draw.focus_ring {
+                        let padding = element.style.padding;
+
+                        draw.rectangle_border(
+                            element_cursor - vec2(padding.left, -padding.top),
+                            element_size + vec2(padding.width(), padding.height()),
+                            element.style.focus_width,
+                            element.style.focus_color,
+                        );
                     }
                 }
-            }
-        })),
-        update_with_max_width: if found_flex && expand_from_below {
-            Some(Box::new(move |extra_size: Vec2| {
-                for flex_child in &flex_children {
-                    flex_child(extra_size);
+
+                if pass == 0 {
+                    draw.set_tint_internal(vec4(1.0, 1.0, 1.0, 1.0));
+                    draw.set_scale(1.0);
                 }
-            }))
-        } else {
-            None
-        },
-    });
+
+                cursor_x += bounding_box.width();
+            }
+
+            cursor_y -= line_size.get().y;
+        }
+    }
 }
 
 pub(crate) fn border_extra(style: &Style, size: Vec2) -> Vec2 {
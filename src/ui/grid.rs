@@ -0,0 +1,420 @@
+use crate::{
+    math::{vec2, Vec2},
+    ui::{
+        frame::{self, render_lines},
+        id::Id,
+        ui::{Element, FLEX_WEIGHT},
+        Align, Spacing, Style, Ui, VertAlign,
+    },
+};
+use std::{cell::Cell, rc::Rc};
+
+/// How wide a column or how tall a row of a `Grid` is.
+#[derive(Clone, Copy, Debug)]
+pub enum Track {
+    Fixed(f32),
+    /// Fits the largest cell in the track.
+    Auto,
+    /// Shares the space left by the other tracks by weight, never smaller than its cells.
+    Fraction(f32),
+}
+
+#[must_use = "You should call .show()"]
+pub struct Grid {
+    columns: Vec<Track>,
+    rows: Vec<Track>,
+    spacing: Vec2,
+    size: Option<Vec2>,
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct GridCell {
+    row: usize,
+    column: usize,
+    row_span: usize,
+    column_span: usize,
+    align: Align,
+    vert_align: VertAlign,
+}
+
+pub struct GridUi<'t, 'a, 'draw, 'show> {
+    ui: &'t mut Ui<'a, 'draw, 'show>,
+    parent_id: Id,
+    cells: Vec<(GridCell, Element<'a>)>,
+}
+
+impl Grid {
+    /// Rows that are not listed in `rows` are `Track::Auto`, as are columns past `columns`.
+    pub fn new(columns: &[Track]) -> Grid {
+        Grid {
+            columns: columns.to_vec(),
+            rows: Vec::new(),
+            spacing: Vec2::ZERO,
+            size: None,
+        }
+    }
+
+    #[inline]
+    pub fn rows(mut self, rows: &[Track]) -> Grid {
+        self.rows = rows.to_vec();
+        self
+    }
+
+    /// Gap between columns and between rows.
+    #[inline]
+    pub fn spacing(mut self, spacing: Vec2) -> Grid {
+        self.spacing = spacing;
+        self
+    }
+
+    /// Fractional rows only grow when the grid is given a height.
+    #[inline]
+    pub fn size(mut self, size: Vec2) -> Grid {
+        self.size = Some(size);
+        self
+    }
+
+    pub fn show(self, ui: &mut Ui, func: impl FnOnce(&mut GridUi)) {
+        let style = ui.style;
+        let parent_id = ui.parent_id.with_child(ui.current_id());
+
+        let mut grid = GridUi {
+            ui,
+            parent_id,
+            cells: Vec::new(),
+        };
+
+        func(&mut grid);
+
+        let GridUi { ui, cells, .. } = grid;
+
+        let column_count = cells
+            .iter()
+            .map(|(cell, _)| cell.column + cell.column_span)
+            .chain([self.columns.len()])
+            .max()
+            .unwrap_or(0);
+
+        let row_count = cells
+            .iter()
+            .map(|(cell, _)| cell.row + cell.row_span)
+            .chain([self.rows.len()])
+            .max()
+            .unwrap_or(0);
+
+        let columns = tracks(&self.columns, column_count);
+        let rows = tracks(&self.rows, row_count);
+
+        let min_widths = measure(
+            &columns,
+            cells.iter().map(|(cell, element)| {
+                (cell.column, cell.column_span, element.content_box.get().x)
+            }),
+            self.spacing.x,
+        );
+
+        let min_heights = measure(
+            &rows,
+            cells
+                .iter()
+                .map(|(cell, element)| (cell.row, cell.row_span, element.content_box.get().y)),
+            self.spacing.y,
+        );
+
+        let mut content_box = vec2(
+            total(&min_widths, self.spacing.x),
+            total(&min_heights, self.spacing.y),
+        );
+
+        if let Some(size) = self.size {
+            if size.x > 0.0 {
+                content_box.x = size.x;
+            }
+            if size.y > 0.0 {
+                content_box.y = content_box.y.max(size.y);
+            }
+        }
+
+        let flex_x = self.size.is_none_or(|size| size.x <= 0.0)
+            && columns
+                .iter()
+                .any(|track| matches!(track, Track::Fraction(_)));
+
+        let spacing = self.spacing;
+
+        let cells = cells
+            .into_iter()
+            .map(|(cell, element)| {
+                let line_size = Rc::new(Cell::new(element.content_box.get()));
+                (cell, vec![vec![element]], [line_size])
+            })
+            .collect::<Vec<_>>();
+
+        ui.current_line.push(Element {
+            content_box: Rc::new(Cell::new(content_box)),
+            id: None,
+            border_extra: Vec2::ZERO,
+            flex_x,
+            flex_y: false,
+            weight: FLEX_WEIGHT,
+            max_size: Vec2::INFINITY,
+            style: container_style(style),
+            render: Some(Box::new(move |draw, cursor, size| {
+                // The cells draw both passes themselves, like the lines of a frame
+                if draw.pass() != 1 {
+                    return;
+                }
+
+                let widths = resolve(&columns, &min_widths, size.x, spacing.x);
+                let heights = resolve(&rows, &min_heights, size.y, spacing.y);
+
+                for (cell, lines, line_sizes) in &cells {
+                    let x = offset(&widths, cell.column, spacing.x);
+                    let y = offset(&heights, cell.row, spacing.y);
+
+                    let cell_size = vec2(
+                        span(&widths, cell.column, cell.column_span, spacing.x),
+                        span(&heights, cell.row, cell.row_span, spacing.y),
+                    );
+
+                    let content_width = lines[0][0].content_box.get().x;
+                    line_sizes[0].set(vec2(content_width, cell_size.y));
+
+                    render_lines(
+                        draw,
+                        cursor + vec2(x, -y),
+                        cell_size,
+                        Vec2::ZERO,
+                        &container_style(style).align(cell.align),
+                        lines,
+                        line_sizes,
+                    );
+                }
+            })),
+            update_with_max_width: None,
+        });
+    }
+}
+
+impl GridCell {
+    #[inline]
+    pub fn new(row: usize, column: usize) -> GridCell {
+        GridCell {
+            row,
+            column,
+            row_span: 1,
+            column_span: 1,
+            align: Align::Left,
+            vert_align: VertAlign::Top,
+        }
+    }
+
+    #[inline]
+    pub fn span(mut self, rows: usize, columns: usize) -> GridCell {
+        self.row_span = rows.max(1);
+        self.column_span = columns.max(1);
+        self
+    }
+
+    #[inline]
+    pub fn align(mut self, align: Align) -> GridCell {
+        self.align = align;
+        self
+    }
+
+    #[inline]
+    pub fn vert_align(mut self, vert_align: VertAlign) -> GridCell {
+        self.vert_align = vert_align;
+        self
+    }
+}
+
+impl From<(usize, usize)> for GridCell {
+    #[inline]
+    fn from((row, column): (usize, usize)) -> GridCell {
+        GridCell::new(row, column)
+    }
+}
+
+impl<'t, 'a, 'draw, 'show> GridUi<'t, 'a, 'draw, 'show> {
+    /// Fills `cell`, either a `GridCell` or a `(row, column)` pair.
+    pub fn cell(&mut self, cell: impl Into<GridCell>, func: impl FnOnce(&mut Ui)) {
+        let cell = cell.into();
+        let style = self.ui.style;
+        let parent_id = self
+            .parent_id
+            .with_child(Id::from_vec2(vec2(cell.column as f32, cell.row as f32)));
+
+        let element = frame::build(
+            self.ui,
+            true,
+            container_style(style).vert_align(cell.vert_align),
+            None,
+            None,
+            parent_id,
+            true,
+            |ui| {
+                ui.style = style;
+                func(ui);
+            },
+        );
+
+        self.cells.push((cell, element));
+    }
+}
+
+/// A style for elements that only arrange their children, so they don't add spacing of their own.
+pub(crate) fn container_style(style: Style) -> Style {
+    style.margin(Spacing::ZERO).padding(Spacing::ZERO)
+}
+
+fn tracks(declared: &[Track], count: usize) -> Vec<Track> {
+    (0..count)
+        .map(|index| declared.get(index).copied().unwrap_or(Track::Auto))
+        .collect()
+}
+
+/// The smallest size of each track that fits its cells, cells spanning several
+/// tracks grow the flexible tracks among them evenly.
+fn measure(
+    tracks: &[Track],
+    cells: impl Iterator<Item = (usize, usize, f32)> + Clone,
+    spacing: f32,
+) -> Vec<f32> {
+    let mut sizes = tracks
+        .iter()
+        .map(|track| match track {
+            Track::Fixed(size) => *size,
+            Track::Auto | Track::Fraction(_) => 0.0,
+        })
+        .collect::<Vec<_>>();
+
+    let flexible = |index: usize| !matches!(tracks[index], Track::Fixed(_));
+
+    for (start, _, size) in cells.clone().filter(|(_, span, _)| *span == 1) {
+        if flexible(start) {
+            sizes[start] = sizes[start].max(size);
+        }
+    }
+
+    for (start, span, size) in cells.filter(|(_, span, _)| *span > 1) {
+        let deficit = size - self::span(&sizes, start, span, spacing);
+        let growable = (start..start + span).filter(|index| flexible(*index));
+        let count = growable.clone().count();
+
+        if deficit > 0.0 && count > 0 {
+            for index in growable {
+                sizes[index] += deficit / count as f32;
+            }
+        }
+    }
+
+    sizes
+}
+
+/// The final track sizes once `available` space is known.
+fn resolve(tracks: &[Track], min_sizes: &[f32], available: f32, spacing: f32) -> Vec<f32> {
+    let mut weights = 0.0;
+    let mut used = spacing * tracks.len().saturating_sub(1) as f32;
+
+    for (track, min_size) in tracks.iter().zip(min_sizes) {
+        match track {
+            Track::Fraction(weight) => weights += weight,
+            Track::Fixed(_) | Track::Auto => used += min_size,
+        }
+    }
+
+    let free = (available - used).max(0.0);
+
+    tracks
+        .iter()
+        .zip(min_sizes)
+        .map(|(track, min_size)| match track {
+            Track::Fraction(weight) if weights > 0.0 => (free * weight / weights).max(*min_size),
+            _ => *min_size,
+        })
+        .collect()
+}
+
+fn total(sizes: &[f32], spacing: f32) -> f32 {
+    span(sizes, 0, sizes.len(), spacing)
+}
+
+fn offset(sizes: &[f32], index: usize, spacing: f32) -> f32 {
+    sizes[..index].iter().map(|size| size + spacing).sum()
+}
+
+fn span(sizes: &[f32], start: usize, count: usize, spacing: f32) -> f32 {
+    sizes[start..start + count].iter().sum::<f32>() + spacing * count.saturating_sub(1) as f32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn missing_tracks_are_auto() {
+        let tracks = tracks(&[Track::Fixed(10.0)], 3);
+
+        assert!(matches!(
+            tracks[..],
+            [Track::Fixed(_), Track::Auto, Track::Auto]
+        ));
+    }
+
+    #[test]
+    fn measure_single_cells() {
+        let tracks = [Track::Fixed(50.0), Track::Auto, Track::Fraction(1.0)];
+        let cells = [(0, 1, 80.0), (1, 1, 30.0), (1, 1, 40.0), (2, 1, 10.0)];
+
+        assert_eq!(
+            measure(&tracks, cells.into_iter(), 10.0),
+            [50.0, 40.0, 10.0]
+        );
+    }
+
+    #[test]
+    fn measure_spanning_cells() {
+        let tracks = [Track::Fixed(50.0), Track::Auto, Track::Auto];
+
+        // 200 wide over 50 + 20 + 0 and two spacings, the auto tracks share the rest
+        let cells = [(1, 1, 20.0), (0, 3, 200.0)];
+        assert_eq!(
+            measure(&tracks, cells.into_iter(), 10.0),
+            [50.0, 75.0, 55.0]
+        );
+
+        // A cell that already fits grows nothing
+        let cells = [(1, 1, 20.0), (0, 2, 60.0)];
+        assert_eq!(measure(&tracks, cells.into_iter(), 10.0), [50.0, 20.0, 0.0]);
+
+        // Fixed tracks never grow
+        let tracks = [Track::Fixed(10.0), Track::Fixed(10.0)];
+        let cells = [(0, 2, 100.0)];
+        assert_eq!(measure(&tracks, cells.into_iter(), 0.0), [10.0, 10.0]);
+    }
+
+    #[test]
+    fn resolve_fractions() {
+        let tracks = [
+            Track::Fixed(100.0),
+            Track::Fraction(1.0),
+            Track::Fraction(3.0),
+        ];
+
+        assert_eq!(
+            resolve(&tracks, &[100.0, 0.0, 0.0], 520.0, 10.0),
+            [100.0, 100.0, 300.0]
+        );
+    }
+
+    #[test]
+    fn resolve_keeps_min_sizes() {
+        let tracks = [Track::Fraction(1.0), Track::Fraction(1.0)];
+        assert_eq!(resolve(&tracks, &[150.0, 0.0], 200.0, 0.0), [150.0, 100.0]);
+
+        // Without free space every track gets the size of its cells
+        let tracks = [Track::Auto, Track::Fraction(1.0)];
+        assert_eq!(resolve(&tracks, &[80.0, 30.0], 50.0, 10.0), [80.0, 30.0]);
+    }
+}
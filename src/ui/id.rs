@@ -1,26 +1,119 @@
 use crate::math::Vec2;
-use std::hash::{DefaultHasher, Hash, Hasher};
+use std::hash::{Hash, Hasher};
 
-#[derive(Copy, Clone, Debug, Hash, Eq, PartialEq)]
-pub(crate) struct Id {
+#[derive(Copy, Clone, Debug, Default, Hash, Eq, PartialEq)]
+pub struct Id {
     hash: u64,
 }
 
+/// FNV-1a, ids end up in `Memory::serialize` so they have to hash the same in
+/// every build, unlike `DefaultHasher`.
+struct IdHasher(u64);
+
+impl IdHasher {
+    #[inline]
+    fn new() -> IdHasher {
+        IdHasher(0xcbf29ce484222325)
+    }
+}
+
+impl Hasher for IdHasher {
+    #[inline]
+    fn finish(&self) -> u64 {
+        self.0
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.0 ^= *byte as u64;
+            self.0 = self.0.wrapping_mul(0x100000001b3);
+        }
+    }
+
+    // Integers hash the same on every platform
+    #[inline]
+    fn write_u16(&mut self, i: u16) {
+        self.write(&i.to_le_bytes());
+    }
+
+    #[inline]
+    fn write_u32(&mut self, i: u32) {
+        self.write(&i.to_le_bytes());
+    }
+
+    #[inline]
+    fn write_u64(&mut self, i: u64) {
+        self.write(&i.to_le_bytes());
+    }
+
+    #[inline]
+    fn write_u128(&mut self, i: u128) {
+        self.write(&i.to_le_bytes());
+    }
+
+    #[inline]
+    fn write_usize(&mut self, i: usize) {
+        self.write_u64(i as u64);
+    }
+
+    #[inline]
+    fn write_i16(&mut self, i: i16) {
+        self.write_u16(i as u16);
+    }
+
+    #[inline]
+    fn write_i32(&mut self, i: i32) {
+        self.write_u32(i as u32);
+    }
+
+    #[inline]
+    fn write_i64(&mut self, i: i64) {
+        self.write_u64(i as u64);
+    }
+
+    #[inline]
+    fn write_i128(&mut self, i: i128) {
+        self.write_u128(i as u128);
+    }
+
+    #[inline]
+    fn write_isize(&mut self, i: isize) {
+        self.write_u64(i as u64);
+    }
+}
+
 impl Id {
     pub fn none() -> Id {
         Id { hash: 0 }
     }
 
+    pub fn new(source: impl Hash) -> Id {
+        let mut hasher = IdHasher::new();
+        source.hash(&mut hasher);
+        let hash = hasher.finish();
+        Id { hash }
+    }
+
     pub fn from_vec2(v: Vec2) -> Id {
-        let mut hasher = DefaultHasher::new();
+        let mut hasher = IdHasher::new();
         (v.x as i32).hash(&mut hasher);
         (v.y as i32).hash(&mut hasher);
         let hash = hasher.finish();
         Id { hash }
     }
 
+    #[inline]
+    pub(crate) fn to_u64(self) -> u64 {
+        self.hash
+    }
+
+    #[inline]
+    pub(crate) fn from_u64(hash: u64) -> Id {
+        Id { hash }
+    }
+
     pub fn with_child(&self, id: Id) -> Id {
-        let mut hasher = DefaultHasher::new();
+        let mut hasher = IdHasher::new();
         self.hash.hash(&mut hasher);
         id.hash.hash(&mut hasher);
         let hash = hasher.finish();
@@ -0,0 +1,305 @@
+use crate::ui::id::Id;
+use std::{
+    any::{Any, TypeId},
+    collections::HashMap,
+    str::FromStr,
+};
+
+type Save = fn(&dyn Any) -> String;
+
+struct Entry {
+    value: Box<dyn Any>,
+    save: Option<(&'static str, Save)>,
+}
+
+/// A value that `Memory` can write out and read back.
+///
+/// `TAG` is written next to the value to tell apart values of different types
+/// persisted for the same id. It must stay the same between versions so saved
+/// state can still be restored, and can't contain spaces.
+pub trait Persist: Default + FromStr + ToString + 'static {
+    const TAG: &'static str;
+}
+
+impl Persist for bool {
+    const TAG: &'static str = "bool";
+}
+
+impl Persist for i32 {
+    const TAG: &'static str = "i32";
+}
+
+impl Persist for f32 {
+    const TAG: &'static str = "f32";
+}
+
+impl Persist for f64 {
+    const TAG: &'static str = "f64";
+}
+
+impl Persist for String {
+    const TAG: &'static str = "string";
+}
+
+/// State that outlives a frame, stored per id and type.
+///
+/// Values stored with `get_persisted_or_default` are also written by `serialize`,
+/// so they can be handed back to `restore` after the application restarts.
+#[derive(Default)]
+pub struct Memory {
+    entries: HashMap<(Id, TypeId), Entry>,
+    restored: HashMap<(Id, String), String>,
+}
+
+impl Memory {
+    pub fn get<T: 'static>(&self, id: Id) -> Option<&T> {
+        self.entries
+            .get(&(id, TypeId::of::<T>()))
+            .and_then(|entry| entry.value.downcast_ref())
+    }
+
+    pub fn get_mut<T: 'static>(&mut self, id: Id) -> Option<&mut T> {
+        self.entries
+            .get_mut(&(id, TypeId::of::<T>()))
+            .and_then(|entry| entry.value.downcast_mut())
+    }
+
+    pub fn get_or_insert_with<T: 'static>(
+        &mut self,
+        id: Id,
+        default: impl FnOnce() -> T,
+    ) -> &mut T {
+        self.entries
+            .entry((id, TypeId::of::<T>()))
+            .or_insert_with(|| Entry {
+                value: Box::new(default()),
+                save: None,
+            })
+            .value
+            .downcast_mut()
+            .unwrap()
+    }
+
+    #[inline]
+    pub fn get_or_default<T: Default + 'static>(&mut self, id: Id) -> &mut T {
+        self.get_or_insert_with(id, T::default)
+    }
+
+    /// Like `get_or_default`, but the value is included in `serialize` and
+    /// starts out as the restored value when there is one.
+    pub fn get_persisted_or_default<T: Persist>(&mut self, id: Id) -> &mut T {
+        let restored = &mut self.restored;
+
+        let entry = self
+            .entries
+            .entry((id, TypeId::of::<T>()))
+            .or_insert_with(|| Entry {
+                value: Box::new(
+                    restored
+                        .remove(&(id, T::TAG.to_owned()))
+                        .and_then(|text| text.parse::<T>().ok())
+                        .unwrap_or_default(),
+                ),
+                save: None,
+            });
+
+        entry.save = Some((T::TAG, |value| {
+            value.downcast_ref::<T>().unwrap().to_string()
+        }));
+        entry.value.downcast_mut().unwrap()
+    }
+
+    pub fn insert<T: 'static>(&mut self, id: Id, value: T) {
+        let save = self
+            .entries
+            .remove(&(id, TypeId::of::<T>()))
+            .and_then(|entry| entry.save);
+
+        self.entries.insert(
+            (id, TypeId::of::<T>()),
+            Entry {
+                value: Box::new(value),
+                save,
+            },
+        );
+    }
+
+    pub fn remove<T: 'static>(&mut self, id: Id) -> Option<T> {
+        self.entries
+            .remove(&(id, TypeId::of::<T>()))
+            .and_then(|entry| entry.value.downcast().ok())
+            .map(|value| *value)
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.clear();
+        self.restored.clear();
+    }
+
+    /// The persisted values as text, one per line, tagged with their type. Restored
+    /// values that were never asked for this run are kept so they aren't lost for
+    /// widgets that weren't shown.
+    pub fn serialize(&self) -> String {
+        let saved = self.entries.iter().filter_map(|((id, _), entry)| {
+            entry
+                .save
+                .map(|(tag, save)| (*id, tag.to_owned(), save(entry.value.as_ref())))
+        });
+
+        let restored = self
+            .restored
+            .iter()
+            .map(|((id, tag), text)| (*id, tag.clone(), text.clone()));
+
+        saved
+            .chain(restored)
+            .map(|(id, tag, text)| format!("{:016x} {} {}\n", id.to_u64(), tag, escape(&text)))
+            .collect()
+    }
+
+    /// Reads the output of `serialize`, malformed lines are skipped.
+    pub fn restore(&mut self, data: &str) {
+        for line in data.lines() {
+            let mut parts = line.splitn(3, ' ');
+
+            let (Some(id), Some(tag), Some(text)) = (parts.next(), parts.next(), parts.next())
+            else {
+                continue;
+            };
+
+            if let Ok(id) = u64::from_str_radix(id, 16) {
+                self.restored
+                    .insert((Id::from_u64(id), tag.to_owned()), unescape(text));
+            }
+        }
+    }
+}
+
+fn escape(text: &str) -> String {
+    text.replace('\\', "\\\\")
+        .replace('\n', "\\n")
+        .replace('\r', "\\r")
+}
+
+fn unescape(text: &str) -> String {
+    let mut result = String::with_capacity(text.len());
+    let mut chars = text.chars();
+
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            result.push(c);
+            continue;
+        }
+
+        match chars.next() {
+            Some('n') => result.push('\n'),
+            Some('r') => result.push('\r'),
+            Some(other) => result.push(other),
+            None => result.push('\\'),
+        }
+    }
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn escape_round_trip() {
+        for text in [
+            "",
+            "plain",
+            "two\nlines",
+            "\n",
+            "crlf\r\n",
+            "trailing\\",
+            "\\n",
+            "\\\\\n\\",
+        ] {
+            let escaped = escape(text);
+
+            assert!(!escaped.contains('\n') && !escaped.contains('\r'));
+            assert_eq!(unescape(&escaped), text);
+        }
+    }
+
+    #[test]
+    fn unescape_trailing_backslash() {
+        assert_eq!(unescape("end\\"), "end\\");
+        assert_eq!(unescape("\\"), "\\");
+    }
+
+    #[test]
+    fn serialize_round_trip() {
+        let id = Id::new("value");
+        let frame_id = Id::new("frame");
+
+        let mut memory = Memory::default();
+        *memory.get_persisted_or_default::<String>(id) = "two\nlines\\".to_owned();
+        *memory.get_persisted_or_default::<f32>(id) = 0.25;
+        memory.insert(frame_id, 1u8);
+
+        let mut restored = Memory::default();
+        restored.restore(&memory.serialize());
+
+        assert_eq!(
+            restored.get_persisted_or_default::<String>(id),
+            "two\nlines\\"
+        );
+        assert_eq!(*restored.get_persisted_or_default::<f32>(id), 0.25);
+        assert_eq!(restored.get::<u8>(frame_id), None);
+    }
+
+    #[test]
+    fn unclaimed_values_are_kept() {
+        let id = Id::new("value");
+
+        let mut memory = Memory::default();
+        memory.restore(&format!("{:016x} bool true\n", id.to_u64()));
+
+        let mut restored = Memory::default();
+        restored.restore(&memory.serialize());
+
+        assert!(*restored.get_persisted_or_default::<bool>(id));
+    }
+
+    #[test]
+    fn tags_are_stable() {
+        let id = Id::new("value");
+
+        let mut memory = Memory::default();
+        *memory.get_persisted_or_default::<bool>(id) = true;
+
+        assert_eq!(<bool as Persist>::TAG, "bool");
+        assert_eq!(
+            memory.serialize(),
+            format!("{:016x} bool true\n", id.to_u64())
+        );
+    }
+
+    #[test]
+    fn malformed_lines_are_skipped() {
+        let id = Id::new("value");
+        let other_id = Id::new("other");
+
+        let data = [
+            String::new(),
+            "not-hex bool true".to_owned(),
+            format!("{:016x}", id.to_u64()),
+            format!("{:016x} bool", id.to_u64()),
+            format!("{:016x} bool yes", other_id.to_u64()),
+            format!("{:016x} f32 1.5", other_id.to_u64()),
+            format!("{:016x} bool true", id.to_u64()),
+        ]
+        .join("\n");
+
+        let mut memory = Memory::default();
+        memory.restore(&data);
+
+        assert!(*memory.get_persisted_or_default::<bool>(id));
+        assert!(!*memory.get_persisted_or_default::<bool>(other_id));
+        assert_eq!(*memory.get_persisted_or_default::<f32>(other_id), 1.5);
+    }
+}
@@ -0,0 +1,310 @@
+use crate::{
+    math::{vec2, Vec2, Vec4},
+    ui::{
+        bounding_box::BoundingBox,
+        collapsing::draw_arrow,
+        color::hover_color,
+        draw_api::DrawApi,
+        frame,
+        id::Id,
+        popup::{self, Placement},
+        ui::Element,
+        FrameStyle, Response, Ui,
+    },
+    window::{Key, KeyRepeat},
+};
+use std::{borrow::Cow, cell::Cell, rc::Rc};
+
+/// The items of an open context menu or submenu.
+pub struct Menu<'t, 'a, 'draw, 'show> {
+    ui: &'t mut Ui<'a, 'draw, 'show>,
+    depth: usize,
+    close: bool,
+}
+
+#[must_use = "You should call .show()"]
+pub struct MenuItem<'a> {
+    label: Cow<'a, str>,
+    shortcut: Option<Cow<'a, str>>,
+    checked: Option<bool>,
+    enabled: bool,
+}
+
+pub(crate) fn context_menu(ui: &mut Ui, response: &Response, func: impl FnOnce(&mut Menu)) {
+    if response.secondary_clicked {
+        ui.state.open_popup = Some(response.id);
+        ui.state.context_menu_pos = ui.state.mouse_pos;
+        ui.state.open_submenus.clear();
+    }
+
+    if ui.state.open_popup != Some(response.id) {
+        return;
+    }
+
+    let popup_id = response.id.with_child(Id::new("context_menu"));
+
+    let clicked = ui.state.mouse_just_pressed
+        || (ui.state.secondary_just_pressed && !response.secondary_clicked);
+
+    if (clicked && !is_hovered(ui, popup_id))
+        || ui.window.is_key_pressed(Key::Escape, KeyRepeat::No)
+    {
+        close(ui);
+        return;
+    }
+
+    let anchor = BoundingBox::new(ui.state.context_menu_pos, Vec2::ZERO);
+
+    let chosen = show_popup(
+        ui,
+        popup_id,
+        Placement::Anchored(Rc::new(Cell::new(anchor))),
+        0,
+        func,
+    );
+
+    if chosen {
+        close(ui);
+    }
+}
+
+/// Shows the items of a menu on top of everything else,
+/// returns true when one of them was chosen.
+pub(crate) fn show_popup(
+    ui: &mut Ui,
+    popup_id: Id,
+    placement: Placement,
+    depth: usize,
+    func: impl FnOnce(&mut Menu),
+) -> bool {
+    let style = ui.style;
+    let mut chosen = false;
+
+    popup::show(ui, popup_id, placement, true, style, |ui| {
+        let mut menu = Menu {
+            ui,
+            depth,
+            close: false,
+        };
+
+        func(&mut menu);
+
+        chosen = menu.close;
+    });
+
+    chosen
+}
+
+/// Whether the pointer is over the menu or any of its open submenus.
+pub(crate) fn is_hovered(ui: &Ui, popup_id: Id) -> bool {
+    ui.response_for(popup_id).hovered
+        || ui
+            .state
+            .open_submenus
+            .iter()
+            .any(|id| ui.response_for(submenu_popup_id(*id)).hovered)
+}
+
+pub(crate) fn close(ui: &mut Ui) {
+    ui.state.open_popup = None;
+    ui.state.open_submenus.clear();
+}
+
+fn submenu_popup_id(id: Id) -> Id {
+    id.with_child(Id::new("menu"))
+}
+
+impl<'t, 'a, 'draw, 'show> Menu<'t, 'a, 'draw, 'show> {
+    #[inline]
+    pub fn item(&mut self, label: impl Into<Cow<'a, str>>) -> Response {
+        MenuItem::new(label).show(self)
+    }
+
+    pub fn separator(&mut self) {
+        let style = self.ui.style;
+        let height = style.text_height / 2.0;
+
+        self.ui
+            .push_ui_element(style, vec2(0.0, height), None, Vec2::ZERO, true, false, {
+                move |draw, cursor, size| {
+                    draw.line(
+                        cursor - vec2(0.0, size.y / 2.0),
+                        cursor + vec2(size.x, -size.y / 2.0),
+                        1.0,
+                        style.border_color,
+                    );
+                }
+            });
+
+        self.ui.next_line();
+    }
+
+    /// A row that opens `func` in a menu beside it while hovered.
+    pub fn submenu(&mut self, label: impl Into<Cow<'a, str>>, func: impl FnOnce(&mut Menu)) {
+        let id = self.ui.current_id();
+        let style = self.ui.style;
+
+        let mut element = self.row(id, label.into(), None, None, style.enabled, true);
+        let anchor = popup::track_anchor(&mut element, &style);
+
+        let open = self.ui.state.open_submenus.get(self.depth) == Some(&id);
+
+        self.ui.current_line.push(element);
+        self.ui.next_line();
+
+        if !open {
+            return;
+        }
+
+        self.close |= show_popup(
+            self.ui,
+            submenu_popup_id(id),
+            Placement::Beside(anchor),
+            self.depth + 1,
+            func,
+        );
+    }
+
+    fn row(
+        &mut self,
+        id: Id,
+        label: Cow<'a, str>,
+        shortcut: Option<Cow<'a, str>>,
+        checked: Option<bool>,
+        enabled: bool,
+        submenu: bool,
+    ) -> Element<'a> {
+        let ui = &mut *self.ui;
+        let style = ui.style;
+
+        let response = if enabled {
+            ui.response_for(id)
+        } else {
+            Response::default()
+        };
+
+        // Hovering a row closes the submenus of its siblings
+        if response.hovered {
+            ui.state.open_submenus.truncate(self.depth);
+
+            if submenu {
+                ui.state.open_submenus.push(id);
+            }
+        }
+
+        let highlighted = response.hovered || ui.state.open_submenus.get(self.depth) == Some(&id);
+
+        let row_style = if highlighted {
+            style.frame_color(hover_color(style.frame_color))
+        } else {
+            style.frame_style(FrameStyle::None)
+        };
+
+        let foreground = if enabled && style.enabled {
+            style.text_color
+        } else {
+            style.inactive_color
+        };
+
+        let label = label.into_owned();
+        let shortcut = shortcut.map(Cow::into_owned);
+        let indicator_size = vec2(style.text_height, style.text_height);
+
+        frame::build(
+            ui,
+            false,
+            row_style,
+            None,
+            enabled.then_some(id),
+            id,
+            true,
+            move |ui| {
+                ui.canvas(indicator_size, move |draw, cursor, size| {
+                    if checked == Some(true) {
+                        draw_checkmark(draw, cursor, size, foreground);
+                    }
+                });
+
+                ui.colored_text(label, foreground);
+                ui.horizontal_spring();
+
+                if let Some(shortcut) = shortcut {
+                    ui.empty_area(vec2(style.text_height, 0.0));
+                    ui.colored_text(shortcut, style.inactive_color);
+                }
+
+                ui.canvas(indicator_size, move |draw, cursor, size| {
+                    if submenu {
+                        draw_arrow(draw, cursor, size, false, foreground);
+                    }
+                });
+            },
+        )
+    }
+}
+
+impl<'a> MenuItem<'a> {
+    pub fn new(label: impl Into<Cow<'a, str>>) -> MenuItem<'a> {
+        MenuItem {
+            label: label.into(),
+            shortcut: None,
+            checked: None,
+            enabled: true,
+        }
+    }
+
+    /// Key combination hint drawn at the right edge, the shortcut itself is up to the caller.
+    #[inline]
+    pub fn shortcut(mut self, shortcut: impl Into<Cow<'a, str>>) -> MenuItem<'a> {
+        self.shortcut = Some(shortcut.into());
+        self
+    }
+
+    #[inline]
+    pub fn checked(mut self, checked: bool) -> MenuItem<'a> {
+        self.checked = Some(checked);
+        self
+    }
+
+    #[inline]
+    pub fn enabled(mut self, enabled: bool) -> MenuItem<'a> {
+        self.enabled = enabled;
+        self
+    }
+
+    /// Pressing the item closes the whole menu.
+    pub fn show(self, menu: &mut Menu<'_, 'a, '_, '_>) -> Response {
+        let id = menu.ui.current_id();
+        let enabled = self.enabled && menu.ui.style.enabled;
+
+        let element = menu.row(id, self.label, self.shortcut, self.checked, enabled, false);
+
+        let response = if enabled {
+            menu.ui.response_for(id)
+        } else {
+            Response::default()
+        };
+
+        if response.pressed {
+            menu.close = true;
+        }
+
+        menu.ui.current_line.push(element);
+        menu.ui.next_line();
+
+        response
+    }
+}
+
+fn draw_checkmark(draw: &DrawApi, cursor: Vec2, size: Vec2, color: Vec4) {
+    let extent = size.x.min(size.y) * 0.5;
+    let center = cursor + vec2(size.x / 2.0, -size.y / 2.0);
+    let width = extent / 5.0;
+
+    let from = center + vec2(-extent / 2.0, 0.0);
+    let corner = center + vec2(-extent / 6.0, -extent / 3.0);
+    let to = center + vec2(extent / 2.0, extent / 3.0);
+
+    draw.line(from, corner, width, color);
+    draw.line(corner, to, width, color);
+}
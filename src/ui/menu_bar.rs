@@ -0,0 +1,263 @@
+use crate::{
+    math::vec2,
+    ui::{
+        color::{held_color, hover_color},
+        frame,
+        id::Id,
+        menu::{self, Menu},
+        popup::{self, Placement},
+        FrameStyle, Ui,
+    },
+    window::{Key, KeyRepeat},
+};
+use std::borrow::Cow;
+
+#[derive(Clone, Default)]
+pub(crate) struct MenuBarState {
+    open: Option<usize>,
+    mnemonics: Vec<Option<char>>,
+    /// Whether the bar was shown this frame, bars that weren't are dropped.
+    pub(crate) shown: bool,
+}
+
+pub struct MenuBar<'t, 'a, 'draw, 'show> {
+    ui: &'t mut Ui<'a, 'draw, 'show>,
+    id: Id,
+    open: Option<usize>,
+    alt_down: bool,
+    chosen: bool,
+    mnemonics: Vec<Option<char>>,
+}
+
+pub(crate) fn show(ui: &mut Ui, func: impl FnOnce(&mut MenuBar)) {
+    let style = ui.style;
+    let id = ui.current_id();
+
+    frame::show(ui, false, style, None, None, true, |ui| {
+        let state = ui.state.menu_bars.remove(&id).unwrap_or_default();
+        let previous_menu = state.open.map(|index| menu_id(id, index));
+
+        let mut open = state.open.filter(|_| ui.state.open_popup == previous_menu);
+
+        navigate(ui, id, &state, &mut open);
+
+        let alt_down = ui.window.is_key_down(Key::LeftAlt) || ui.window.is_key_down(Key::RightAlt);
+
+        if open.map(|index| menu_id(id, index)) != previous_menu {
+            ui.state.open_submenus.clear();
+        }
+
+        match open {
+            Some(index) => ui.state.open_popup = Some(menu_id(id, index)),
+            None if previous_menu.is_some() && ui.state.open_popup == previous_menu => {
+                ui.state.open_popup = None;
+            }
+            None => (),
+        }
+
+        let mut bar = MenuBar {
+            ui,
+            id,
+            open,
+            alt_down,
+            chosen: false,
+            mnemonics: Vec::new(),
+        };
+
+        func(&mut bar);
+
+        let MenuBar {
+            mut open,
+            chosen,
+            mnemonics,
+            ..
+        } = bar;
+
+        if chosen {
+            menu::close(ui);
+            open = None;
+        }
+
+        ui.horizontal_spring();
+        ui.state.menu_bars.insert(
+            id,
+            MenuBarState {
+                open,
+                mnemonics,
+                shown: true,
+            },
+        );
+    });
+}
+
+/// Opens, closes and switches menus from the responses and keys of this frame,
+/// using the menus that were shown last frame.
+fn navigate(ui: &mut Ui, id: Id, state: &MenuBarState, open: &mut Option<usize>) {
+    let window = ui.window;
+    let count = state.mnemonics.len();
+    let mut on_button = false;
+
+    for index in 0..count {
+        let response = ui.widget_response_for(menu_id(id, index));
+
+        if response.pressed {
+            on_button = true;
+            *open = if *open == Some(index) {
+                None
+            } else {
+                Some(index)
+            };
+        } else if response.hovered && open.is_some() {
+            *open = Some(index);
+        }
+    }
+
+    if let Some(current) = *open {
+        let popup_id = popup_id(menu_id(id, current));
+
+        if window.is_key_pressed(Key::Escape, KeyRepeat::No)
+            || (ui.state.mouse_just_pressed && !on_button && !menu::is_hovered(ui, popup_id))
+        {
+            *open = None;
+        } else if window.is_key_pressed(Key::Left, KeyRepeat::Yes) {
+            *open = Some((current + count - 1) % count);
+        } else if window.is_key_pressed(Key::Right, KeyRepeat::Yes) {
+            *open = Some((current + 1) % count);
+        }
+    }
+
+    let alt_down = window.is_key_down(Key::LeftAlt) || window.is_key_down(Key::RightAlt);
+
+    if alt_down && !ui.state.focus_blocked {
+        for key in window.get_keys_pressed(KeyRepeat::No) {
+            let pressed = key_char(key);
+
+            if let Some(index) = state
+                .mnemonics
+                .iter()
+                .position(|mnemonic| mnemonic.is_some() && *mnemonic == pressed)
+            {
+                *open = Some(index);
+            }
+        }
+    }
+}
+
+fn menu_id(bar_id: Id, index: usize) -> Id {
+    bar_id.with_child(Id::from_vec2(vec2(index as f32, 0.0)))
+}
+
+fn popup_id(menu_id: Id) -> Id {
+    menu_id.with_child(Id::new("menu"))
+}
+
+fn key_char(key: Key) -> Option<char> {
+    match key as u8 {
+        index @ 0..=9 => Some((b'0' + index) as char),
+        index @ 10..=35 => Some((b'a' + index - 10) as char),
+        _ => None,
+    }
+}
+
+/// Strips the `&` that marks the mnemonic, "&File" is opened with Alt+F.
+/// Without a marker the first letter is used.
+fn parse_mnemonic(label: &str) -> (String, Option<usize>) {
+    match label.find('&') {
+        Some(marker) if marker + 1 < label.len() => {
+            let mut text = label.to_owned();
+            text.remove(marker);
+            (text, Some(marker))
+        }
+        _ => (
+            label.to_owned(),
+            label
+                .char_indices()
+                .find(|(_, c)| c.is_alphanumeric())
+                .map(|(i, _)| i),
+        ),
+    }
+}
+
+impl<'t, 'a, 'draw, 'show> MenuBar<'t, 'a, 'draw, 'show> {
+    pub fn menu(&mut self, label: impl Into<Cow<'a, str>>, func: impl FnOnce(&mut Menu)) {
+        let index = self.mnemonics.len();
+        let id = menu_id(self.id, index);
+        let open = self.open == Some(index);
+
+        let ui = &mut *self.ui;
+        let style = ui.style;
+        let response = ui.widget_response_for(id);
+
+        let (label, mnemonic) = parse_mnemonic(&label.into());
+
+        self.mnemonics.push(
+            mnemonic.and_then(|byte| label[byte..].chars().next().map(|c| c.to_ascii_lowercase())),
+        );
+
+        let button_style = if response.held || open {
+            style.frame_color(held_color(style.frame_color))
+        } else if response.hovered {
+            style.frame_color(hover_color(style.frame_color))
+        } else {
+            style.frame_style(FrameStyle::None)
+        };
+
+        let foreground = if style.enabled {
+            style.text_color
+        } else {
+            style.inactive_color
+        };
+
+        // Underline the mnemonic while Alt is held
+        let underline = mnemonic.filter(|_| self.alt_down).map(|byte| {
+            let font = style.font.unwrap_or_default();
+            let end = label[byte..]
+                .chars()
+                .next()
+                .map_or(label.len(), |c| byte + c.len_utf8());
+
+            let before = ui
+                .draw
+                .calc_text_size(&label[..byte], style.text_height, 10000.0, font);
+            let letter =
+                ui.draw
+                    .calc_text_size(&label[byte..end], style.text_height, 10000.0, font);
+
+            (before.x, letter.x)
+        });
+
+        let mut button = frame::build(
+            ui,
+            false,
+            button_style,
+            None,
+            style.enabled.then_some(id),
+            id,
+            false,
+            move |ui| ui.colored_text(label, foreground),
+        );
+
+        if let (Some((offset, width)), Some(render)) = (underline, button.render.take()) {
+            let text_offset = vec2(
+                style.margin.left + style.padding.left + offset,
+                -style.margin.top - style.padding.top - style.text_height,
+            );
+
+            button.render = Some(Box::new(move |draw, cursor, size| {
+                render(draw, cursor, size);
+
+                if draw.pass() == 1 {
+                    draw.rectangle(cursor + text_offset, vec2(width, 1.0), foreground);
+                }
+            }));
+        }
+
+        let anchor = popup::track_anchor(&mut button, &button_style);
+
+        ui.current_line.push(button);
+
+        if open {
+            self.chosen |= menu::show_popup(ui, popup_id(id), Placement::Anchored(anchor), 0, func);
+        }
+    }
+}
@@ -1,41 +1,83 @@
 use crate::{
     cairo::Context,
     math::{vec2, vec4, Vec2},
-    window::{MouseButton, MouseMode, Window},
+    window::{CursorStyle, Key, KeyRepeat, MouseButton, MouseMode, Window},
 };
 use bounding_box::BoundingBox;
 use draw_api::DrawApi;
-use id::Id;
+use menu_bar::MenuBarState;
 use std::{
-    collections::HashMap,
+    cell::Cell,
+    collections::{HashMap, HashSet},
     marker::PhantomData,
     mem,
+    rc::Rc,
     time::{Duration, Instant},
 };
+use tree::TreeState;
+use virtual_list::VirtualListState;
 
+mod animation;
 mod bounding_box;
+mod collapsing;
 mod color;
+mod combo_box;
 mod debug;
+mod drag_value;
 mod draw_api;
+mod flex;
+mod floating_window;
 mod font;
 mod frame;
+mod grid;
 mod id;
 mod image;
+mod memory;
+mod menu;
+mod menu_bar;
+mod modal;
+mod panel;
+mod popup;
 mod response;
+mod scroll_area;
+mod slider;
 mod spacing;
+mod split;
 mod style;
+mod table;
+mod tabs;
 mod textedit;
+mod tree;
 mod ui;
+mod virtual_list;
 
+pub use animation::{Animate, Easing};
+pub use drag_value::DragValue;
+pub use flex::Flex;
+pub use floating_window::FloatingWindow;
 pub use font::Font;
 pub use frame::FrameStyle;
+pub use grid::{Grid, GridCell, GridUi, Track};
+pub use id::Id;
 pub use image::Image;
+pub use memory::{Memory, Persist};
+pub use menu::{Menu, MenuItem};
+pub use menu_bar::MenuBar;
+pub use panel::{Panel, Side};
 pub use response::Response;
+pub use slider::{Numeric, Slider};
 pub use spacing::Spacing;
+pub use split::Split;
 pub use style::Style;
+pub use table::{Column, Table, TableResponse};
 pub use textedit::TextEdit;
+pub use tree::Tree;
 pub use ui::Ui;
 
+// Pointer travel before a held press turns into a drag
+const DRAG_THRESHOLD: f32 = 3.0;
+const LONG_PRESS_TIME: Duration = Duration::from_millis(600);
+
 #[derive(Clone, Copy, Debug)]
 pub enum Align {
     Right,
@@ -52,6 +94,7 @@ pub enum VertAlign {
 
 pub struct OuiContext {
     state: HashMap<usize, OuiState>,
+    memory: Memory,
     _marker: PhantomData<*const ()>,
 }
 
@@ -59,27 +102,123 @@ impl OuiContext {
     pub fn new() -> OuiContext {
         OuiContext {
             state: HashMap::new(),
+            memory: Memory::default(),
             _marker: PhantomData,
         }
     }
+
+    /// Whether an animation was still running in the last frame, so the
+    /// application should keep drawing frames even without input.
+    pub fn is_animating(&self) -> bool {
+        self.state.values().any(|state| state.animating)
+    }
+
+    /// The cursor asked for by the widgets in the last frame, for the application
+    /// to pass on to `Window::set_cursor_style`.
+    pub fn cursor_style(&self) -> CursorStyle {
+        self.state
+            .values()
+            .map(|state| state.cursor)
+            .find(|cursor| *cursor != CursorStyle::Arrow)
+            .unwrap_or(CursorStyle::Arrow)
+    }
+
+    /// Ids used by more than one widget in the last frame, these widgets steal each
+    /// others clicks. Tell them apart with `Ui::push_id` or `Ui::id_source`, debug
+    /// mode highlights them.
+    pub fn duplicate_ids(&self) -> Vec<Id> {
+        self.state
+            .values()
+            .flat_map(|state| state.duplicate_ids.iter().copied())
+            .collect()
+    }
+
+    /// State kept between frames, see `Memory::serialize` for keeping it between runs.
+    #[inline]
+    pub fn memory(&mut self) -> &mut Memory {
+        &mut self.memory
+    }
 }
 
-struct OuiState {
-    bounding_boxes: Vec<(Id, BoundingBox)>,
-    mouse_pressed: bool,
-    mouse_pressed_pos: Vec2,
-    mouse_pressed_id: Id,
+pub(crate) struct OuiState {
+    bounding_boxes: Vec<(Id, BoundingBox, u32)>,
+    pub(crate) mouse_pos: Vec2,
+    pub(crate) mouse_just_pressed: bool,
+    pub(crate) mouse_pressed: bool,
+    pub(crate) mouse_pressed_pos: Vec2,
+    pub(crate) mouse_pressed_id: Id,
+    mouse_pressed_time: Instant,
     mouse_released_time: Instant,
+    dragging: bool,
+    long_pressed: bool,
+    secondary_down: bool,
+    pub(crate) secondary_just_pressed: bool,
+    middle_down: bool,
+    pub(crate) scroll_wheel: Vec2,
+    pub(crate) drag_start_value: f64,
+    pub(crate) value_edit: Option<(Id, TextEdit)>,
+    pub(crate) open_popup: Option<Id>,
+    pub(crate) context_menu_pos: Vec2,
+    pub(crate) open_submenus: Vec<Id>,
+    pub(crate) menu_bars: HashMap<Id, MenuBarState>,
+    pub(crate) hover_start: HashMap<Id, Instant>,
+    pub(crate) top_modal: Option<Id>,
+    pub(crate) next_top_modal: Option<Id>,
+    pub(crate) focus_blocked: bool,
+    pub(crate) focused: Option<Id>,
+    pub(crate) focusables: Vec<Id>,
+    pub(crate) focus_activated: bool,
+    focus_visible: bool,
+    pub(crate) trees: HashMap<Id, TreeState>,
+    pub(crate) virtual_lists: HashMap<Id, Rc<VirtualListState>>,
+    pub(crate) splits: HashMap<Id, Rc<Cell<f32>>>,
+    /// Floating windows from back to front.
+    pub(crate) window_order: Vec<Id>,
+    pub(crate) screen_size: Vec2,
+    pub(crate) animating: bool,
+    pub(crate) cursor: CursorStyle,
+    duplicate_ids: HashSet<Id>,
 }
 
 impl Default for OuiState {
     fn default() -> OuiState {
         OuiState {
             bounding_boxes: Vec::new(),
+            mouse_pos: Vec2::ZERO,
+            mouse_just_pressed: false,
             mouse_pressed: false,
             mouse_pressed_pos: Vec2::ZERO,
             mouse_pressed_id: Id::none(),
+            mouse_pressed_time: Instant::now(),
             mouse_released_time: Instant::now() - Duration::from_millis(1000),
+            dragging: false,
+            long_pressed: false,
+            secondary_down: false,
+            secondary_just_pressed: false,
+            middle_down: false,
+            scroll_wheel: Vec2::ZERO,
+            drag_start_value: 0.0,
+            value_edit: None,
+            open_popup: None,
+            context_menu_pos: Vec2::ZERO,
+            open_submenus: Vec::new(),
+            menu_bars: HashMap::new(),
+            hover_start: HashMap::new(),
+            top_modal: None,
+            next_top_modal: None,
+            focus_blocked: false,
+            focused: None,
+            focusables: Vec::new(),
+            focus_activated: false,
+            focus_visible: false,
+            trees: HashMap::new(),
+            virtual_lists: HashMap::new(),
+            splits: HashMap::new(),
+            window_order: Vec::new(),
+            screen_size: Vec2::ZERO,
+            animating: false,
+            cursor: CursorStyle::Arrow,
+            duplicate_ids: HashSet::new(),
         }
     }
 }
@@ -128,6 +267,18 @@ impl<'ctx> Oui<'ctx> {
         let mouse_pos = window.get_mouse_pos(MouseMode::Pass).unwrap_or_default();
         let mouse_pos = vec2(mouse_pos.0, -mouse_pos.1);
 
+        let scroll_wheel = window.get_scroll_wheel().unwrap_or_default();
+
+        let mouse_delta = mouse_pos - state.mouse_pos;
+
+        state.mouse_pos = mouse_pos;
+        state.screen_size = screen_size;
+        state.top_modal = state.next_top_modal.take();
+        state.focus_blocked = state.top_modal.is_some();
+        state.scroll_wheel = vec2(scroll_wheel.0, scroll_wheel.1);
+        state.animating = false;
+        state.cursor = CursorStyle::Arrow;
+
         let responses = {
             let mut found_first = false;
             let left_down = window.get_mouse_down(MouseButton::Left);
@@ -142,19 +293,64 @@ impl<'ctx> Oui<'ctx> {
             if pressed {
                 state.mouse_pressed_pos = mouse_pos;
                 state.mouse_pressed = true;
+                state.mouse_pressed_id = Id::none();
+                state.mouse_pressed_time = Instant::now();
+                state.long_pressed = false;
+            }
+
+            state.mouse_just_pressed = pressed;
+
+            let drag_started = state.mouse_pressed
+                && !state.dragging
+                && (mouse_pos - state.mouse_pressed_pos).length() > DRAG_THRESHOLD;
+
+            if drag_started {
+                state.dragging = true;
             }
 
+            let drag_stopped = released && state.dragging;
+
             if released {
                 state.mouse_pressed = false;
                 state.mouse_released_time = Instant::now();
+                state.dragging = false;
+            }
+
+            let long_pressed = state.mouse_pressed
+                && !state.dragging
+                && !state.long_pressed
+                && state.mouse_pressed_time.elapsed() >= LONG_PRESS_TIME;
+
+            if long_pressed {
+                state.long_pressed = true;
             }
 
+            let secondary_down = window.get_mouse_down(MouseButton::Right);
+            let secondary_pressed = secondary_down && !state.secondary_down;
+            state.secondary_down = secondary_down;
+            state.secondary_just_pressed = secondary_pressed;
+
+            let middle_down = window.get_mouse_down(MouseButton::Middle);
+            let middle_pressed = middle_down && !state.middle_down;
+            state.middle_down = middle_down;
+
+            let mut found_secondary = false;
+            let mut found_middle = false;
+
+            let mouse_layer = state
+                .bounding_boxes
+                .iter()
+                .filter(|(_, bb, _)| bb.intersect(mouse_pos))
+                .map(|(_, _, layer)| *layer)
+                .max()
+                .unwrap_or(0);
+
             state
                 .bounding_boxes
                 .iter()
                 .rev()
-                .map(|(id, bb)| {
-                    let hovered = bb.intersect(mouse_pos);
+                .map(|(id, bb, layer)| {
+                    let hovered = *layer >= mouse_layer && bb.intersect(mouse_pos);
 
                     let relative_mouse_pos = mouse_pos - bb.top_left;
 
@@ -175,6 +371,23 @@ impl<'ctx> Oui<'ctx> {
                         found_first = true;
                     }
 
+                    let secondary_clicked = !found_secondary && hovered && secondary_pressed;
+                    let middle_clicked = !found_middle && hovered && middle_pressed;
+
+                    found_secondary |= secondary_clicked;
+                    found_middle |= middle_clicked;
+
+                    let owner = state.mouse_pressed_id == *id;
+                    let dragged = owner && state.dragging;
+                    let drag_stopped = owner && drag_stopped;
+                    let was_hovered = state.hover_start.contains_key(id);
+
+                    let (drag_delta, drag_total) = if dragged || drag_stopped {
+                        (mouse_delta, mouse_pos - state.mouse_pressed_pos)
+                    } else {
+                        (Vec2::ZERO, Vec2::ZERO)
+                    };
+
                     (
                         *id,
                         Response {
@@ -183,31 +396,71 @@ impl<'ctx> Oui<'ctx> {
                             released,
                             double_clicked,
                             held,
+                            changed: false,
+                            secondary_clicked,
+                            middle_clicked,
+                            hover_entered: hovered && !was_hovered,
+                            hover_exited: !hovered && was_hovered,
+                            drag_started: owner && drag_started,
+                            dragged,
+                            drag_stopped,
+                            drag_delta,
+                            drag_total,
+                            scroll_delta: if hovered {
+                                state.scroll_wheel
+                            } else {
+                                Vec2::ZERO
+                            },
+                            long_pressed: owner && hovered && long_pressed,
+                            focused: false,
                             relative_mouse_pos,
+                            id: *id,
                         },
                     )
                 })
                 .collect::<Vec<(Id, Response)>>()
         };
 
+        state
+            .hover_start
+            .retain(|id, _| responses.iter().any(|(r_id, r)| r_id == id && r.hovered));
+
+        for (id, _) in responses.iter().filter(|(_, r)| r.hovered) {
+            state.hover_start.entry(*id).or_insert_with(Instant::now);
+        }
+
+        update_focus(state, window);
+
         let style = self.style.align(self.style.align.unwrap_or(Align::Left));
 
+        let mut overlays = Vec::new();
+
         let mut ui = Ui {
             draw: &mut draw,
+            window,
             responses: &responses,
+            state,
+            memory: &mut self.ctx.memory,
+            overlays: &mut overlays,
             style,
             current_line: Vec::new(),
             lines: Vec::new(),
             parent_id: Id::from_vec2(vec2(0.0, 0.0)),
+            id_origin: (0, 0),
+            next_id: None,
+            panels: Vec::new(),
         };
 
         frame::show(&mut ui, false, style, None, None, true, func);
 
+        let focus_ring = ui.state.focused.filter(|_| ui.state.focus_visible);
+
         let element = &ui.current_line[0];
 
         if self.fill {
             let content_box = element.content_box.get();
             let mut extra_x = 0.0;
+            let mut extra_y = 0.0;
 
             if content_box.x < screen_size.x {
                 extra_x = screen_size.x - content_box.x;
@@ -217,13 +470,14 @@ impl<'ctx> Oui<'ctx> {
             }
 
             if content_box.y < screen_size.y {
+                extra_y = screen_size.y - content_box.y;
                 element
                     .content_box
                     .set(vec2(element.content_box.get().x, screen_size.y));
             }
 
             if let Some(update_with_max_width) = &element.update_with_max_width {
-                update_with_max_width(vec2(extra_x, 0.0));
+                update_with_max_width(vec2(extra_x, extra_y));
             }
         }
 
@@ -234,6 +488,8 @@ impl<'ctx> Oui<'ctx> {
                 -element.style.padding.top - element.style.margin.top,
             );
 
+        draw.focus_ring = focus_ring;
+
         for pass in 0..2 {
             draw.set_pass(pass);
 
@@ -246,12 +502,51 @@ impl<'ctx> Oui<'ctx> {
             }
         }
 
+        // Windows that weren't shown this frame lose their place
+        state.window_order.retain(|id| {
+            overlays.iter().any(|overlay| {
+                overlay.id == *id && matches!(overlay.placement, popup::Placement::Floating(_))
+            })
+        });
+
+        popup::render(&mut draw, &overlays, screen_size, &state.window_order);
+
+        if state.virtual_lists.values().any(|list| list.is_stale()) {
+            state.animating = true;
+        }
+
+        // The elements built this frame still hold on to the state they use,
+        // whatever else belongs to widgets that weren't shown
+        state
+            .virtual_lists
+            .retain(|_, list| Rc::strong_count(list) > 1);
+        state
+            .splits
+            .retain(|_, length| Rc::strong_count(length) > 1);
+        state
+            .trees
+            .retain(|_, tree| mem::replace(&mut tree.shown, false));
+        state
+            .menu_bars
+            .retain(|_, bar| mem::replace(&mut bar.shown, false));
+
         let bounding_boxes = mem::take(&mut draw.boxes);
 
+        // Widgets sharing an id steal each others clicks
+        let mut seen = HashSet::new();
+
+        state.duplicate_ids.clear();
+
+        for (id, _, _) in bounding_boxes.iter() {
+            if !seen.insert(*id) {
+                state.duplicate_ids.insert(*id);
+            }
+        }
+
         if style.debug {
             let mut found_first = false;
 
-            for (_, bb) in bounding_boxes.iter().rev() {
+            for (_, bb, _) in bounding_boxes.iter().rev() {
                 if !found_first && bb.intersect(mouse_pos) {
                     draw.rectangle(bb.top_left, bb.size, vec4(0.0, 0.0, 1.0, 0.2));
                     found_first = true;
@@ -260,6 +555,12 @@ impl<'ctx> Oui<'ctx> {
                 draw.rectangle_border(bb.top_left, bb.size, 1.0, vec4(1.0, 0.0, 0.0, 1.0));
             }
 
+            for (id, bb, _) in bounding_boxes.iter() {
+                if state.duplicate_ids.contains(id) {
+                    draw.rectangle(bb.top_left, bb.size, vec4(1.0, 0.0, 1.0, 0.4));
+                }
+            }
+
             draw.circle(
                 mouse_pos - vec2(2.0, -2.0),
                 1.0,
@@ -271,3 +572,47 @@ impl<'ctx> Oui<'ctx> {
         state.bounding_boxes = bounding_boxes;
     }
 }
+
+/// Moves keyboard focus with Tab and Shift+Tab through the widgets that were
+/// focusable last frame, a click anywhere else drops it.
+fn update_focus(state: &mut OuiState, window: &Window) {
+    let focusables = mem::take(&mut state.focusables);
+
+    if state.mouse_just_pressed {
+        state.focus_visible = false;
+
+        if state.focused != Some(state.mouse_pressed_id) {
+            state.focused = None;
+        }
+    }
+
+    if state
+        .focused
+        .is_some_and(|focused| !focusables.contains(&focused))
+    {
+        state.focused = None;
+    }
+
+    if !focusables.is_empty() && window.is_key_pressed(Key::Tab, KeyRepeat::Yes) {
+        let count = focusables.len();
+        let backwards = window.is_key_down(Key::LeftShift) || window.is_key_down(Key::RightShift);
+        let current = state
+            .focused
+            .and_then(|focused| focusables.iter().position(|id| *id == focused));
+
+        let next = match (current, backwards) {
+            (Some(index), false) => (index + 1) % count,
+            (Some(index), true) => (index + count - 1) % count,
+            (None, false) => 0,
+            (None, true) => count - 1,
+        };
+
+        state.focused = Some(focusables[next]);
+        state.focus_visible = true;
+    }
+
+    state.focus_activated = state.focused.is_some()
+        && (window.is_key_pressed(Key::Enter, KeyRepeat::No)
+            || window.is_key_pressed(Key::NumPadEnter, KeyRepeat::No)
+            || window.is_key_pressed(Key::Space, KeyRepeat::No));
+}
@@ -0,0 +1,40 @@
+use crate::{
+    math::{vec4, Vec4},
+    ui::{
+        id::Id,
+        popup::{self, Placement},
+        Style, Ui,
+    },
+    window::{Key, KeyRepeat},
+};
+use std::{hash::Hash, mem};
+
+const DIM_COLOR: Vec4 = vec4(0.0, 0.0, 0.0, 0.4);
+
+pub(crate) fn show(
+    ui: &mut Ui,
+    id_source: impl Hash,
+    style: Style,
+    func: impl FnOnce(&mut Ui),
+) -> bool {
+    let id = ui.parent_id.with_child(Id::new(id_source));
+
+    // The modal opened last in a frame is the one on top, only it gets keyboard focus.
+    ui.state.next_top_modal = Some(id);
+
+    let top = ui.state.top_modal == Some(id);
+    let focus_blocked = mem::replace(
+        &mut ui.state.focus_blocked,
+        !top && ui.state.top_modal.is_some(),
+    );
+
+    let close = top
+        && ui.state.open_popup.is_none()
+        && ui.window.is_key_pressed(Key::Escape, KeyRepeat::No);
+
+    popup::show(ui, id, Placement::Modal(DIM_COLOR), true, style, func);
+
+    ui.state.focus_blocked = focus_blocked;
+
+    !close
+}
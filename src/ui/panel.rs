@@ -0,0 +1,510 @@
+use crate::{
+    math::{vec2, Vec2},
+    ui::{
+        bounding_box::BoundingBox,
+        draw_api::DrawApi,
+        frame::{self, render_lines},
+        grid::container_style,
+        id::Id,
+        memory::Persist,
+        spacing,
+        ui::{Element, UpdateWithMaxWidth},
+        Style, Ui,
+    },
+    window::CursorStyle,
+};
+use std::{cell::Cell, fmt, hash::Hash, rc::Rc, str::FromStr};
+
+const SPLITTER_WIDTH: f32 = 6.0;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Side {
+    Top,
+    Bottom,
+    Left,
+    Right,
+}
+
+/// A panel docked to an edge of the area of a `Ui`, usually the window.
+///
+/// Panels take their space in the order they are shown, the first panel gets the
+/// whole edge. The other widgets of the `Ui` are laid out in what is left over.
+#[must_use = "You should call .show()"]
+pub struct Panel {
+    side: Side,
+    id: Id,
+    resizable: bool,
+    default_size: Option<f32>,
+    min_size: f32,
+    max_size: f32,
+}
+
+/// The size the panel was dragged to, `None` until it is resized.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+struct PanelState {
+    size: Option<f32>,
+}
+
+pub(crate) struct DockedPanel<'a> {
+    side: Side,
+    size: f32,
+    element: Element<'a>,
+    splitter: Option<(Id, bool)>,
+}
+
+/// Lines that are stretched to the space the dock gives them.
+pub(crate) struct DockArea<'a> {
+    lines: Vec<Vec<Element<'a>>>,
+    line_sizes: Vec<Rc<Cell<Vec2>>>,
+    min_size: Vec2,
+    grow: Box<UpdateWithMaxWidth>,
+    applied: Cell<Vec2>,
+}
+
+impl Panel {
+    #[inline]
+    pub fn top(id_source: impl Hash) -> Panel {
+        Panel::new(Side::Top, id_source)
+    }
+
+    #[inline]
+    pub fn bottom(id_source: impl Hash) -> Panel {
+        Panel::new(Side::Bottom, id_source)
+    }
+
+    #[inline]
+    pub fn left(id_source: impl Hash) -> Panel {
+        Panel::new(Side::Left, id_source)
+    }
+
+    #[inline]
+    pub fn right(id_source: impl Hash) -> Panel {
+        Panel::new(Side::Right, id_source)
+    }
+
+    fn new(side: Side, id_source: impl Hash) -> Panel {
+        Panel {
+            side,
+            id: Id::new(id_source),
+            resizable: false,
+            default_size: None,
+            min_size: 0.0,
+            max_size: f32::INFINITY,
+        }
+    }
+
+    /// Adds a splitter handle on the inner edge, the size it is dragged to is
+    /// kept in `Memory` and included in `Memory::serialize`.
+    #[inline]
+    pub fn resizable(mut self, resizable: bool) -> Panel {
+        self.resizable = resizable;
+        self
+    }
+
+    /// Width of a left or right panel, height of a top or bottom one, until it is
+    /// resized. Defaults to the size of the content.
+    #[inline]
+    pub fn default_size(mut self, size: f32) -> Panel {
+        self.default_size = Some(size);
+        self
+    }
+
+    #[inline]
+    pub fn min_size(mut self, size: f32) -> Panel {
+        self.min_size = size;
+        self
+    }
+
+    #[inline]
+    pub fn max_size(mut self, size: f32) -> Panel {
+        self.max_size = size;
+        self
+    }
+
+    pub fn show(self, ui: &mut Ui, func: impl FnOnce(&mut Ui)) {
+        let style = ui.style;
+        let id = ui.parent_id.with_child(self.id);
+        let splitter_id = id.with_child(Id::new("splitter"));
+
+        let element = frame::build(ui, false, style, None, None, id, true, func);
+
+        let bounding_box = spacing::bounding_box(
+            element.content_box.get(),
+            element.style.margin,
+            element.style.padding,
+        );
+
+        let content_size = match self.side {
+            Side::Top | Side::Bottom => bounding_box.height(),
+            Side::Left | Side::Right => bounding_box.width(),
+        };
+
+        let stored = *ui.memory.get_persisted_or_default::<PanelState>(id);
+
+        let mut size = stored.size.or(self.default_size).unwrap_or(content_size);
+
+        size = size.max(self.min_size).min(self.max_size);
+
+        let response = ui.response_for(splitter_id);
+
+        if self.resizable && response.dragged {
+            let delta = response.drag_delta;
+
+            let delta = match self.side {
+                Side::Top => -delta.y,
+                Side::Bottom => delta.y,
+                Side::Left => delta.x,
+                Side::Right => -delta.x,
+            };
+
+            size = (size + delta).max(self.min_size).min(self.max_size);
+            ui.memory.insert(id, PanelState { size: Some(size) });
+        }
+
+        if self.resizable && (response.hovered || response.dragged) {
+            ui.set_cursor(match self.side {
+                Side::Top | Side::Bottom => CursorStyle::ResizeUpDown,
+                Side::Left | Side::Right => CursorStyle::ResizeLeftRight,
+            });
+        }
+
+        ui.panels.push(DockedPanel {
+            side: self.side,
+            size,
+            element,
+            splitter: self
+                .resizable
+                .then_some((splitter_id, response.hovered || response.dragged)),
+        });
+    }
+}
+
+impl<'a> DockArea<'a> {
+    /// An area holding a single element, like a frame built with `frame::build`.
+    pub(crate) fn new(element: Element<'a>) -> DockArea<'a> {
+        let bounding_box = spacing::bounding_box(
+            element.content_box.get(),
+            element.style.margin,
+            element.style.padding,
+        );
+        let min_size = vec2(bounding_box.width(), bounding_box.height());
+
+        let line_size = Rc::new(Cell::new(min_size));
+
+        let grow = Box::new({
+            let content_box = element.content_box.clone();
+            let update_with_max_width = element.update_with_max_width.clone();
+            let line_size = line_size.clone();
+
+            move |extra_size: Vec2| {
+                content_box.set(content_box.get() + extra_size);
+                line_size.set(line_size.get() + extra_size);
+
+                if let Some(update_with_max_width) = update_with_max_width.as_ref() {
+                    update_with_max_width(extra_size);
+                }
+            }
+        });
+
+        DockArea {
+            lines: vec![vec![element]],
+            line_sizes: vec![line_size],
+            min_size,
+            grow,
+            applied: Cell::new(Vec2::ZERO),
+        }
+    }
+
+    #[inline]
+    pub(crate) fn min_size(&self) -> Vec2 {
+        self.min_size
+    }
+
+    pub(crate) fn render(&self, draw: &mut DrawApi, cursor: Vec2, size: Vec2, style: &Style) {
+        let extra = (size - self.min_size).max(Vec2::ZERO);
+        let delta = extra - self.applied.get();
+
+        if delta != Vec2::ZERO {
+            self.applied.set(extra);
+            (self.grow)(delta);
+        }
+
+        draw.push_clip(cursor, size);
+        render_lines(
+            draw,
+            cursor,
+            size,
+            Vec2::ZERO,
+            style,
+            &self.lines,
+            &self.line_sizes,
+        );
+        draw.pop_clip();
+    }
+}
+
+/// Lays out `panels` along the edges and the already laid out lines of the `Ui`
+/// in the middle, as a single element that fills the area.
+pub(crate) fn dock<'a>(
+    panels: Vec<DockedPanel<'a>>,
+    style: Style,
+    lines: Vec<Vec<Element<'a>>>,
+    line_sizes: Vec<Rc<Cell<Vec2>>>,
+    total_size: Vec2,
+    flex_children: Vec<Box<UpdateWithMaxWidth>>,
+) -> Element<'a> {
+    let mut content_box = total_size;
+
+    for panel in panels.iter().rev() {
+        let bounding_box = spacing::bounding_box(
+            panel.element.content_box.get(),
+            panel.element.style.margin,
+            panel.element.style.padding,
+        );
+
+        match panel.side {
+            Side::Top | Side::Bottom => {
+                content_box.x = content_box.x.max(bounding_box.width());
+                content_box.y += panel.size;
+            }
+            Side::Left | Side::Right => {
+                content_box.x += panel.size;
+                content_box.y = content_box.y.max(bounding_box.height());
+            }
+        }
+    }
+
+    let central = DockArea {
+        lines,
+        line_sizes,
+        min_size: total_size,
+        grow: Box::new(move |extra_size: Vec2| {
+            for flex_child in &flex_children {
+                flex_child(extra_size);
+            }
+        }),
+        applied: Cell::new(Vec2::ZERO),
+    };
+
+    let panels = panels
+        .into_iter()
+        .map(|panel| {
+            let area = DockArea::new(panel.element);
+
+            (panel.side, panel.size, area, panel.splitter)
+        })
+        .collect::<Vec<_>>();
+
+    Element {
+        content_box: Rc::new(Cell::new(content_box)),
+        id: None,
+        border_extra: Vec2::ZERO,
+        flex_x: true,
+        flex_y: false,
+        weight: vec2(1.0, 1.0),
+        max_size: Vec2::INFINITY,
+        style: container_style(style),
+        render: Some(Box::new(move |draw, cursor, size| {
+            // The areas draw both passes themselves, like the lines of a frame
+            if draw.pass() != 1 {
+                return;
+            }
+
+            let (placed, free_pos, free_size) = layout(
+                panels
+                    .iter()
+                    .map(|(side, panel_size, ..)| (*side, *panel_size)),
+                size,
+            );
+            let mut splitters = Vec::new();
+
+            for ((side, _, area, splitter), (pos, extent)) in panels.iter().zip(placed) {
+                area.render(
+                    draw,
+                    cursor + vec2(pos.x, -pos.y),
+                    extent,
+                    &container_style(style),
+                );
+
+                if let Some((splitter_id, active)) = splitter {
+                    let edge = splitter_edge(*side, pos, extent);
+
+                    splitters.push((
+                        *splitter_id,
+                        *active,
+                        BoundingBox::new(cursor + edge.top_left, edge.size),
+                    ));
+                }
+            }
+
+            central.render(
+                draw,
+                cursor + vec2(free_pos.x, -free_pos.y),
+                free_size,
+                &style,
+            );
+
+            // Handles go last so they win over the widgets along the edges
+            for (splitter_id, active, edge) in splitters {
+                if active {
+                    draw.rectangle(edge.top_left, edge.size, style.focus_color);
+                }
+
+                draw.push_box(splitter_id, edge);
+            }
+        })),
+        update_with_max_width: None,
+    }
+}
+
+/// Takes the space of each panel off its side of an area of `size`, in the order
+/// the panels were shown. Gives the position and size of every panel and of what
+/// is left over for the center, with positions growing downwards.
+fn layout(
+    panels: impl Iterator<Item = (Side, f32)>,
+    size: Vec2,
+) -> (Vec<(Vec2, Vec2)>, Vec2, Vec2) {
+    let mut free_pos = Vec2::ZERO;
+    let mut free_size = size;
+
+    let placed = panels
+        .map(|(side, panel_size)| match side {
+            Side::Top => {
+                let height = panel_size.min(free_size.y);
+                let pos = free_pos;
+
+                free_size.y -= height;
+                free_pos.y += height;
+
+                (pos, vec2(free_size.x, height))
+            }
+            Side::Bottom => {
+                let height = panel_size.min(free_size.y);
+                free_size.y -= height;
+
+                (free_pos + vec2(0.0, free_size.y), vec2(free_size.x, height))
+            }
+            Side::Left => {
+                let width = panel_size.min(free_size.x);
+                let pos = free_pos;
+
+                free_size.x -= width;
+                free_pos.x += width;
+
+                (pos, vec2(width, free_size.y))
+            }
+            Side::Right => {
+                let width = panel_size.min(free_size.x);
+                free_size.x -= width;
+
+                (free_pos + vec2(free_size.x, 0.0), vec2(width, free_size.y))
+            }
+        })
+        .collect();
+
+    (placed, free_pos, free_size)
+}
+
+/// The splitter handle on the inner edge of a panel placed by `layout`, relative
+/// to the cursor of the dock.
+fn splitter_edge(side: Side, pos: Vec2, extent: Vec2) -> BoundingBox {
+    match side {
+        Side::Top => BoundingBox::new(
+            vec2(pos.x, -(pos.y + extent.y - SPLITTER_WIDTH / 2.0)),
+            vec2(extent.x, SPLITTER_WIDTH),
+        ),
+        Side::Bottom => BoundingBox::new(
+            vec2(pos.x, -(pos.y - SPLITTER_WIDTH / 2.0)),
+            vec2(extent.x, SPLITTER_WIDTH),
+        ),
+        Side::Left => BoundingBox::new(
+            vec2(pos.x + extent.x - SPLITTER_WIDTH / 2.0, -pos.y),
+            vec2(SPLITTER_WIDTH, extent.y),
+        ),
+        Side::Right => BoundingBox::new(
+            vec2(pos.x - SPLITTER_WIDTH / 2.0, -pos.y),
+            vec2(SPLITTER_WIDTH, extent.y),
+        ),
+    }
+}
+
+impl Persist for PanelState {
+    const TAG: &'static str = "panel";
+}
+
+impl fmt::Display for PanelState {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.size {
+            Some(size) => write!(f, "{}", size),
+            None => write!(f, "-"),
+        }
+    }
+}
+
+impl FromStr for PanelState {
+    type Err = ();
+
+    fn from_str(text: &str) -> Result<PanelState, ()> {
+        let size = match text.trim() {
+            "-" => None,
+            size => Some(size.parse().map_err(|_| ())?),
+        };
+
+        Ok(PanelState { size })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn panel_state_round_trip() {
+        for size in [None, Some(0.0), Some(180.5)] {
+            let state = PanelState { size };
+
+            assert_eq!(state.to_string().parse::<PanelState>(), Ok(state));
+        }
+    }
+
+    #[test]
+    fn panels_take_their_size_and_leave_the_rest_to_the_center() {
+        let panels = [
+            (Side::Top, 50.0),
+            (Side::Left, 100.0),
+            (Side::Right, 60.0),
+            (Side::Bottom, 40.0),
+        ];
+
+        let (placed, center_pos, center_size) = layout(panels.into_iter(), vec2(400.0, 300.0));
+
+        assert_eq!(
+            placed,
+            [
+                (vec2(0.0, 0.0), vec2(400.0, 50.0)),
+                (vec2(0.0, 50.0), vec2(100.0, 250.0)),
+                (vec2(340.0, 50.0), vec2(60.0, 250.0)),
+                (vec2(100.0, 260.0), vec2(240.0, 40.0)),
+            ]
+        );
+        assert_eq!(center_pos, vec2(100.0, 50.0));
+        assert_eq!(center_size, vec2(240.0, 210.0));
+    }
+
+    #[test]
+    fn panels_larger_than_the_area_are_cut() {
+        let panels = [(Side::Left, 300.0), (Side::Right, 300.0)];
+
+        let (placed, center_pos, center_size) = layout(panels.into_iter(), vec2(400.0, 300.0));
+
+        assert_eq!(
+            placed,
+            [
+                (vec2(0.0, 0.0), vec2(300.0, 300.0)),
+                (vec2(300.0, 0.0), vec2(100.0, 300.0)),
+            ]
+        );
+        assert_eq!(center_pos, vec2(300.0, 0.0));
+        assert_eq!(center_size, vec2(0.0, 300.0));
+    }
+}
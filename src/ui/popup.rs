@@ -0,0 +1,209 @@
+use crate::{
+    math::{vec2, vec4, Vec2, Vec4},
+    ui::{
+        bounding_box::BoundingBox, debug, draw_api::DrawApi, frame, id::Id, ui::Element, Style, Ui,
+    },
+};
+use std::{cell::Cell, rc::Rc};
+
+pub(crate) enum Placement {
+    /// Below the anchor box which is filled in when the anchoring widget is
+    /// rendered, flipped above it when there is no room.
+    Anchored(Rc<Cell<BoundingBox>>),
+    /// To the right of the anchor box with the tops aligned, flipped to the
+    /// left side when there is no room. Used for submenus.
+    Beside(Rc<Cell<BoundingBox>>),
+    /// Centered on the screen, everything beneath is dimmed with the color
+    /// and blocked from the pointer.
+    Modal(Vec4),
+    /// At a fixed top left corner. Drawn below all other overlays, in the
+    /// order of `OuiState::window_order`.
+    Floating(Vec2),
+}
+
+pub(crate) struct Overlay<'a> {
+    pub(crate) id: Id,
+    pub(crate) placement: Placement,
+    pub(crate) hit_test: bool,
+    pub(crate) element: Element<'a>,
+}
+
+/// Builds a frame that is drawn on top of all other content. Overlays opened
+/// from inside `func` end up above this one.
+/// Without `hit_test` the frame is only drawn and never takes the pointer.
+pub(crate) fn show(
+    ui: &mut Ui,
+    id: Id,
+    placement: Placement,
+    hit_test: bool,
+    style: Style,
+    func: impl FnOnce(&mut Ui),
+) {
+    let index = ui.overlays.len();
+
+    let element = frame::build(ui, false, style, None, None, id, true, func);
+
+    ui.overlays.insert(
+        index,
+        Overlay {
+            id,
+            placement,
+            hit_test,
+            element,
+        },
+    );
+}
+
+/// Makes `element` record where it was rendered, padding included, so an
+/// overlay can be placed next to it.
+pub(crate) fn track_anchor(element: &mut Element, style: &Style) -> Rc<Cell<BoundingBox>> {
+    let anchor = Rc::new(Cell::new(BoundingBox::new(Vec2::ZERO, Vec2::ZERO)));
+
+    if let Some(render) = element.render.take() {
+        let anchor = anchor.clone();
+        let padding = style.padding;
+
+        element.render = Some(Box::new(move |draw, cursor, size| {
+            anchor.set(BoundingBox::new(
+                cursor - vec2(padding.left, -padding.top),
+                size + padding.size(),
+            ));
+
+            render(draw, cursor, size);
+        }));
+    }
+
+    anchor
+}
+
+pub(crate) fn render(
+    draw: &mut DrawApi,
+    overlays: &[Overlay],
+    screen_size: Vec2,
+    window_order: &[Id],
+) {
+    let floating = |overlay: &&Overlay| matches!(overlay.placement, Placement::Floating(_));
+
+    let windows = window_order.iter().filter_map(|id| {
+        overlays
+            .iter()
+            .filter(floating)
+            .find(|overlay| overlay.id == *id)
+    });
+    let others = overlays.iter().filter(|overlay| !floating(overlay));
+
+    for (layer, overlay) in windows.chain(others).enumerate() {
+        draw.set_layer(layer as u32 + 1);
+
+        let element = &overlay.element;
+        let style = element.style;
+        let spacing = style.margin.size() + style.padding.size();
+
+        if let Placement::Anchored(anchor) = &overlay.placement {
+            let anchor = anchor.get();
+            let content_box = element.content_box.get();
+
+            if content_box.x + spacing.x < anchor.size.x {
+                let extra_size = vec2(anchor.size.x - content_box.x - spacing.x, 0.0);
+
+                element.content_box.set(content_box + extra_size);
+
+                if let Some(update_with_max_width) = &element.update_with_max_width {
+                    update_with_max_width(extra_size);
+                }
+            }
+        }
+
+        let size = element.content_box.get() + spacing;
+
+        let top_left = match &overlay.placement {
+            Placement::Anchored(anchor) => {
+                let anchor = anchor.get();
+
+                let mut top_left = vec2(anchor.top_left.x, anchor.top_left.y - anchor.size.y);
+
+                if top_left.y - size.y < -screen_size.y && anchor.top_left.y + size.y <= 0.0 {
+                    top_left.y = anchor.top_left.y + size.y;
+                }
+
+                if top_left.x + size.x > screen_size.x {
+                    top_left.x = anchor.top_left.x + anchor.size.x - size.x;
+                }
+
+                vec2(top_left.x.min(screen_size.x - size.x).max(0.0), top_left.y)
+            }
+            Placement::Beside(anchor) => {
+                let anchor = anchor.get();
+
+                let mut left = anchor.top_left.x + anchor.size.x;
+
+                if left + size.x > screen_size.x {
+                    left = anchor.top_left.x - size.x;
+                }
+
+                let top = anchor.top_left.y.max(size.y - screen_size.y).min(0.0);
+
+                vec2(left.min(screen_size.x - size.x).max(0.0), top)
+            }
+            Placement::Floating(top_left) => *top_left,
+            Placement::Modal(dim_color) => {
+                draw.rectangle(Vec2::ZERO, screen_size, *dim_color);
+
+                vec2(
+                    (screen_size.x - size.x) / 2.0,
+                    -(screen_size.y - size.y) / 2.0,
+                )
+            }
+        };
+
+        if overlay.hit_test {
+            match overlay.placement {
+                Placement::Anchored(_) | Placement::Beside(_) | Placement::Floating(_) => {
+                    draw.push_box(overlay.id, BoundingBox::new(top_left, size));
+                }
+                Placement::Modal(_) => {
+                    draw.push_box(overlay.id, BoundingBox::new(Vec2::ZERO, screen_size));
+                }
+            }
+        }
+
+        let element_cursor = top_left
+            + vec2(
+                style.padding.left + style.margin.left,
+                -style.padding.top - style.margin.top,
+            );
+
+        for pass in 0..2 {
+            draw.set_pass(pass);
+
+            if style.debug && pass == 1 {
+                debug::draw_content_boxes(draw, top_left, element, element.content_box.get().y);
+            }
+
+            if pass == 0 {
+                draw.set_tint_internal(style.shadow_color);
+                draw.set_scale(style.shadow_scale);
+            }
+
+            if pass == 1 || style.shadow_dir.is_some() {
+                let cursor = element_cursor
+                    + if pass == 0 {
+                        style.shadow_dir.unwrap_or(Vec2::ZERO)
+                    } else {
+                        Vec2::ZERO
+                    };
+
+                if let Some(render) = element.render.as_ref() {
+                    render(draw, cursor, element.content_box.get());
+                }
+            }
+
+            if pass == 0 {
+                draw.set_tint_internal(vec4(1.0, 1.0, 1.0, 1.0));
+                draw.set_scale(1.0);
+            }
+        }
+    }
+
+    draw.set_layer(0);
+}
@@ -1,4 +1,8 @@
-use crate::math::Vec2;
+use crate::{
+    math::Vec2,
+    ui::{id::Id, menu, Menu, Ui},
+    window::MouseButton,
+};
 
 #[derive(Copy, Clone, Default)]
 pub struct Response {
@@ -7,5 +11,51 @@ pub struct Response {
     pub released: bool,
     pub double_clicked: bool,
     pub held: bool,
+    pub changed: bool,
+    pub secondary_clicked: bool,
+    pub middle_clicked: bool,
+    /// The pointer moved onto the widget this frame.
+    pub hover_entered: bool,
+    /// The pointer left the widget this frame.
+    pub hover_exited: bool,
+    pub drag_started: bool,
+    /// Held and moved past the drag threshold, keeps going when the pointer leaves the widget.
+    pub dragged: bool,
+    pub drag_stopped: bool,
+    /// Pointer movement since last frame while dragged, y points up like the layout.
+    pub drag_delta: Vec2,
+    /// Pointer movement since the press that started the drag.
+    pub drag_total: Vec2,
+    /// Scroll wheel movement while hovered.
+    pub scroll_delta: Vec2,
+    /// Held in place long enough to count as a long press, set for a single frame.
+    pub long_pressed: bool,
+    /// Has keyboard focus, Enter or Space presses a focused button.
+    pub focused: bool,
     pub relative_mouse_pos: Vec2,
+    pub(crate) id: Id,
+}
+
+impl Response {
+    /// The id of the widget, for keeping state about it in `Ui::memory`.
+    #[inline]
+    pub fn id(&self) -> Id {
+        self.id
+    }
+
+    #[inline]
+    pub fn clicked_by(&self, button: MouseButton) -> bool {
+        match button {
+            MouseButton::Left => self.pressed,
+            MouseButton::Middle => self.middle_clicked,
+            MouseButton::Right => self.secondary_clicked,
+        }
+    }
+
+    /// Opens a menu at the pointer when the widget is right-clicked. It closes
+    /// when an item is chosen, on Escape or on a click outside of it.
+    #[inline]
+    pub fn context_menu(&self, ui: &mut Ui, func: impl FnOnce(&mut Menu)) {
+        menu::context_menu(ui, self, func)
+    }
 }
@@ -0,0 +1,350 @@
+use crate::{
+    math::{vec2, Vec2},
+    ui::{
+        bounding_box::BoundingBox,
+        color::{held_color, hover_color},
+        draw_api::DrawApi,
+        frame,
+        id::Id,
+        memory::Persist,
+        ui::{Element, FLEX_WEIGHT},
+        Style, Ui,
+    },
+};
+use std::{cell::Cell, fmt, rc::Rc, str::FromStr};
+
+pub(crate) const SCROLLBAR_WIDTH: f32 = 10.0;
+pub(crate) const MIN_THUMB_LENGTH: f32 = 20.0;
+pub(crate) const SCROLL_LINES_PER_STEP: f32 = 3.0;
+
+#[derive(Clone, Copy, Default)]
+pub(crate) struct ScrollState {
+    offset: Vec2,
+    drag_start_offset: Vec2,
+}
+
+#[derive(Clone, Copy, Default)]
+pub(crate) struct Scrollbar {
+    pub(crate) visible: bool,
+    pub(crate) thumb_pos: f32,
+    pub(crate) thumb_length: f32,
+    pub(crate) thumb_hovered: bool,
+    pub(crate) thumb_held: bool,
+}
+
+pub(crate) fn show(ui: &mut Ui, style: Style, size: Vec2, func: impl FnOnce(&mut Ui)) {
+    let id = ui.current_id();
+
+    let mut child_ui = Ui {
+        draw: ui.draw,
+        window: ui.window,
+        responses: ui.responses,
+        state: ui.state,
+        memory: ui.memory,
+        overlays: ui.overlays,
+        style,
+        current_line: Vec::new(),
+        lines: Vec::new(),
+        parent_id: ui.parent_id.with_child(id),
+        id_origin: (0, 0),
+        next_id: None,
+        panels: Vec::new(),
+    };
+    func(&mut child_ui);
+
+    let (lines, line_sizes, mut content_size, flex_children) = child_ui.layout();
+
+    let mut viewport = Vec2::ZERO;
+    let mut bars = Vec2::ZERO;
+
+    for _ in 0..2 {
+        viewport = vec2(
+            if size.x > 0.0 {
+                size.x
+            } else {
+                content_size.x + bars.x
+            },
+            if size.y > 0.0 {
+                size.y
+            } else {
+                content_size.y + bars.y
+            },
+        );
+
+        bars = vec2(
+            if content_size.y > viewport.y - bars.y {
+                SCROLLBAR_WIDTH
+            } else {
+                0.0
+            },
+            if content_size.x > viewport.x - bars.x {
+                SCROLLBAR_WIDTH
+            } else {
+                0.0
+            },
+        );
+    }
+
+    let inner_size = (viewport - bars).max(Vec2::ZERO);
+
+    let expand = size.x <= 0.0 && !flex_children.is_empty();
+
+    if !expand && content_size.x < inner_size.x {
+        for flex_child in &flex_children {
+            flex_child(vec2(inner_size.x - content_size.x, 0.0));
+        }
+        content_size.x = inner_size.x;
+    }
+
+    let max_offset = (content_size - inner_size).max(Vec2::ZERO);
+
+    let vertical_thumb_id = id.with_child(Id::from_vec2(vec2(0.0, -1.0)));
+    let vertical_track_id = id.with_child(Id::from_vec2(vec2(0.0, -2.0)));
+    let horizontal_thumb_id = id.with_child(Id::from_vec2(vec2(-1.0, 0.0)));
+    let horizontal_track_id = id.with_child(Id::from_vec2(vec2(-2.0, 0.0)));
+
+    let response = ui.response_for(id);
+    let vertical_thumb = ui.response_for(vertical_thumb_id);
+    let vertical_track = ui.response_for(vertical_track_id);
+    let horizontal_thumb = ui.response_for(horizontal_thumb_id);
+    let horizontal_track = ui.response_for(horizontal_track_id);
+
+    let mut scroll = *ui.memory.get_persisted_or_default::<ScrollState>(id);
+
+    if response.hovered {
+        let step = style.text_height * SCROLL_LINES_PER_STEP;
+        let wheel = ui.state.scroll_wheel;
+
+        if max_offset.x > 0.0 && wheel.x != 0.0 {
+            scroll.offset.x -= wheel.x * step;
+            ui.state.scroll_wheel.x = 0.0;
+        }
+
+        if max_offset.y > 0.0 && wheel.y != 0.0 {
+            scroll.offset.y -= wheel.y * step;
+            ui.state.scroll_wheel.y = 0.0;
+        }
+    }
+
+    let thumb_length = |inner: f32, content: f32| {
+        (inner * inner / content.max(1.0))
+            .max(MIN_THUMB_LENGTH)
+            .min(inner)
+    };
+
+    let vertical_travel = inner_size.y - thumb_length(inner_size.y, content_size.y);
+    let horizontal_travel = inner_size.x - thumb_length(inner_size.x, content_size.x);
+
+    if vertical_thumb.pressed || horizontal_thumb.pressed {
+        scroll.drag_start_offset = scroll.offset;
+    }
+
+    let mouse_delta = ui.state.mouse_pos - ui.state.mouse_pressed_pos;
+
+    let vertical_dragged = ui.state.mouse_pressed && ui.state.mouse_pressed_id == vertical_thumb_id;
+    let horizontal_dragged =
+        ui.state.mouse_pressed && ui.state.mouse_pressed_id == horizontal_thumb_id;
+
+    if vertical_dragged && vertical_travel > 0.0 {
+        scroll.offset.y =
+            scroll.drag_start_offset.y - mouse_delta.y * max_offset.y / vertical_travel;
+    }
+
+    if horizontal_dragged && horizontal_travel > 0.0 {
+        scroll.offset.x =
+            scroll.drag_start_offset.x + mouse_delta.x * max_offset.x / horizontal_travel;
+    }
+
+    if vertical_track.pressed {
+        let thumb_pos = scroll.offset.y / max_offset.y.max(1.0) * vertical_travel;
+
+        if -vertical_track.relative_mouse_pos.y < thumb_pos {
+            scroll.offset.y -= inner_size.y;
+        } else {
+            scroll.offset.y += inner_size.y;
+        }
+    }
+
+    if horizontal_track.pressed {
+        let thumb_pos = scroll.offset.x / max_offset.x.max(1.0) * horizontal_travel;
+
+        if horizontal_track.relative_mouse_pos.x < thumb_pos {
+            scroll.offset.x -= inner_size.x;
+        } else {
+            scroll.offset.x += inner_size.x;
+        }
+    }
+
+    scroll.offset = scroll.offset.clamp(Vec2::ZERO, max_offset);
+
+    ui.memory.insert(id, scroll);
+
+    let offset = scroll.offset;
+
+    let vertical = Scrollbar {
+        visible: bars.x > 0.0,
+        thumb_pos: offset.y / max_offset.y.max(1.0) * vertical_travel,
+        thumb_length: thumb_length(inner_size.y, content_size.y),
+        thumb_hovered: vertical_thumb.hovered,
+        thumb_held: vertical_dragged,
+    };
+
+    let horizontal = Scrollbar {
+        visible: bars.y > 0.0,
+        thumb_pos: offset.x / max_offset.x.max(1.0) * horizontal_travel,
+        thumb_length: thumb_length(inner_size.x, content_size.x),
+        thumb_hovered: horizontal_thumb.hovered,
+        thumb_held: horizontal_dragged,
+    };
+
+    let border_extra = frame::border_extra(&style, viewport);
+
+    ui.current_line.push(Element {
+        content_box: Rc::new(Cell::new(viewport + 2.0 * border_extra)),
+        id: Some(id),
+        border_extra,
+        flex_x: expand,
+        flex_y: false,
+        weight: FLEX_WEIGHT,
+        max_size: Vec2::INFINITY,
+        style,
+        render: Some(Box::new(move |draw, parent_cursor, total_size| {
+            frame::render_frame(draw, &style, parent_cursor, total_size);
+
+            if draw.pass() != 1 {
+                return;
+            }
+
+            let inner_size = vec2(total_size.x - 2.0 * border_extra.x - bars.x, inner_size.y);
+            let content_size = vec2(content_size.x.max(inner_size.x), content_size.y);
+
+            let inner_cursor = parent_cursor + vec2(border_extra.x, -border_extra.y);
+
+            draw.push_clip(inner_cursor, inner_size);
+
+            frame::render_lines(
+                draw,
+                parent_cursor + vec2(-offset.x, offset.y),
+                content_size + 2.0 * border_extra,
+                border_extra,
+                &style,
+                &lines,
+                &line_sizes,
+            );
+
+            draw.pop_clip();
+
+            if vertical.visible {
+                let track_cursor = inner_cursor + vec2(inner_size.x, 0.0);
+                let track_size = vec2(SCROLLBAR_WIDTH, inner_size.y);
+
+                draw_scrollbar(
+                    draw,
+                    &style,
+                    track_cursor,
+                    track_size,
+                    track_cursor - vec2(0.0, vertical.thumb_pos),
+                    vec2(SCROLLBAR_WIDTH, vertical.thumb_length),
+                    &vertical,
+                );
+
+                draw.push_box(
+                    vertical_track_id,
+                    BoundingBox::new(track_cursor, track_size),
+                );
+                draw.push_box(
+                    vertical_thumb_id,
+                    BoundingBox::new(
+                        track_cursor - vec2(0.0, vertical.thumb_pos),
+                        vec2(SCROLLBAR_WIDTH, vertical.thumb_length),
+                    ),
+                );
+            }
+
+            if horizontal.visible {
+                let track_cursor = inner_cursor - vec2(0.0, inner_size.y);
+                let track_size = vec2(inner_size.x, SCROLLBAR_WIDTH);
+
+                draw_scrollbar(
+                    draw,
+                    &style,
+                    track_cursor,
+                    track_size,
+                    track_cursor + vec2(horizontal.thumb_pos, 0.0),
+                    vec2(horizontal.thumb_length, SCROLLBAR_WIDTH),
+                    &horizontal,
+                );
+
+                draw.push_box(
+                    horizontal_track_id,
+                    BoundingBox::new(track_cursor, track_size),
+                );
+                draw.push_box(
+                    horizontal_thumb_id,
+                    BoundingBox::new(
+                        track_cursor + vec2(horizontal.thumb_pos, 0.0),
+                        vec2(horizontal.thumb_length, SCROLLBAR_WIDTH),
+                    ),
+                );
+            }
+        })),
+        update_with_max_width: if expand {
+            Some(Rc::new(move |extra_size: Vec2| {
+                for flex_child in &flex_children {
+                    flex_child(extra_size);
+                }
+            }))
+        } else {
+            None
+        },
+    });
+}
+
+pub(crate) fn draw_scrollbar(
+    draw: &mut DrawApi,
+    style: &Style,
+    track_cursor: Vec2,
+    track_size: Vec2,
+    thumb_cursor: Vec2,
+    thumb_size: Vec2,
+    scrollbar: &Scrollbar,
+) {
+    draw.rectangle(track_cursor, track_size, hover_color(style.frame_color));
+
+    let thumb_color = if scrollbar.thumb_held {
+        held_color(style.inactive_color)
+    } else if scrollbar.thumb_hovered {
+        hover_color(style.inactive_color)
+    } else {
+        style.inactive_color
+    };
+
+    draw.rectangle_rounded(thumb_cursor, thumb_size, SCROLLBAR_WIDTH / 2.0, thumb_color);
+}
+
+impl Persist for ScrollState {
+    const TAG: &'static str = "scroll";
+}
+
+impl fmt::Display for ScrollState {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} {}", self.offset.x, self.offset.y)
+    }
+}
+
+impl FromStr for ScrollState {
+    type Err = ();
+
+    fn from_str(text: &str) -> Result<ScrollState, ()> {
+        let parts = text.split_whitespace().collect::<Vec<_>>();
+
+        let [x, y] = parts[..] else {
+            return Err(());
+        };
+
+        Ok(ScrollState {
+            offset: vec2(x.parse().map_err(|_| ())?, y.parse().map_err(|_| ())?),
+            drag_start_offset: Vec2::ZERO,
+        })
+    }
+}
@@ -0,0 +1,400 @@
+use crate::{
+    math::{vec2, Vec2},
+    ui::{
+        color::{held_color, hover_color},
+        id::Id,
+        Align, Response, TextEdit, Ui,
+    },
+    window::{Key, KeyRepeat},
+};
+use std::ops::RangeInclusive;
+
+pub trait Numeric: Copy {
+    fn to_f64(self) -> f64;
+    fn from_f64(value: f64) -> Self;
+}
+
+impl Numeric for f32 {
+    #[inline]
+    fn to_f64(self) -> f64 {
+        self as f64
+    }
+
+    #[inline]
+    fn from_f64(value: f64) -> Self {
+        value as f32
+    }
+}
+
+impl Numeric for f64 {
+    #[inline]
+    fn to_f64(self) -> f64 {
+        self
+    }
+
+    #[inline]
+    fn from_f64(value: f64) -> Self {
+        value
+    }
+}
+
+#[must_use = "You should call .show()"]
+pub struct Slider<'v, T: Numeric> {
+    value: &'v mut T,
+    range: RangeInclusive<T>,
+    width: Option<f32>,
+    step: Option<f64>,
+    logarithmic: bool,
+    decimals: Option<usize>,
+    suffix: String,
+}
+
+impl<'v, T: Numeric> Slider<'v, T> {
+    #[inline]
+    pub fn new(value: &'v mut T, range: RangeInclusive<T>) -> Slider<'v, T> {
+        Slider {
+            value,
+            range,
+            width: None,
+            step: None,
+            logarithmic: false,
+            decimals: None,
+            suffix: String::new(),
+        }
+    }
+
+    /// Width of the slider track, defaults to eight times the text height.
+    #[inline]
+    pub fn width(mut self, width: f32) -> Slider<'v, T> {
+        self.width = Some(width);
+        self
+    }
+
+    #[inline]
+    pub fn step(mut self, step: f64) -> Slider<'v, T> {
+        self.step = Some(step);
+        self
+    }
+
+    /// Only has an effect when both ends of the range are positive.
+    #[inline]
+    pub fn logarithmic(mut self, logarithmic: bool) -> Slider<'v, T> {
+        self.logarithmic = logarithmic;
+        self
+    }
+
+    #[inline]
+    pub fn decimals(mut self, decimals: usize) -> Slider<'v, T> {
+        self.decimals = Some(decimals);
+        self
+    }
+
+    #[inline]
+    pub fn suffix(mut self, suffix: impl Into<String>) -> Slider<'v, T> {
+        self.suffix = suffix.into();
+        self
+    }
+
+    pub fn show(self, ui: &mut Ui) -> Response {
+        let style = ui.style;
+        // The start of the range is on the left even when it is the larger end
+        let start = self.range.start().to_f64();
+        let end = self.range.end().to_f64();
+        let min = start.min(end);
+        let logarithmic = self.logarithmic && start > 0.0 && end > 0.0;
+
+        let to_t = |value: f64| to_t(value, start, end, logarithmic);
+        let from_t = |t: f64| from_t(t, start, end, logarithmic);
+
+        let width = self.width.unwrap_or(style.text_height * 8.0);
+        let height = style.text_height;
+        let knob_radius = height * 0.4;
+        let travel = (width - 2.0 * knob_radius).max(1.0);
+
+        let id = ui.current_id();
+        let mut response = ui.widget_response();
+        let dragging = style.enabled && ui.is_dragging(id);
+
+        let old_value = self.value.to_f64();
+        let mut value = old_value;
+
+        if response.pressed || dragging {
+            let t = (response.relative_mouse_pos.x - knob_radius) / travel;
+            value = clamp_range(
+                snap(from_t(t.clamp(0.0, 1.0) as f64), self.step, min),
+                start,
+                end,
+            );
+        }
+
+        response.focused = ui.focusable(id, response.pressed);
+
+        if response.focused {
+            let window = ui.window;
+            let direction = window.is_key_pressed(Key::Right, KeyRepeat::Yes) as i32
+                - window.is_key_pressed(Key::Left, KeyRepeat::Yes) as i32;
+
+            // The arrow keys move one step, or a hundredth of the track without a step
+            if direction != 0 {
+                let direction = direction as f64;
+                let sign = if end < start { -1.0 } else { 1.0 };
+
+                value = match self.step {
+                    Some(step) if step > 0.0 => {
+                        snap(value + sign * direction * step, self.step, min)
+                    }
+                    _ => from_t((to_t(value) + direction / 100.0).clamp(0.0, 1.0)),
+                };
+                value = clamp_range(value, start, end);
+            }
+        }
+
+        let t = to_t(value) as f32;
+
+        let knob_color = if response.held || dragging {
+            held_color(style.frame_color)
+        } else if response.hovered {
+            hover_color(style.frame_color)
+        } else {
+            style.frame_color
+        };
+
+        let foreground = if style.enabled {
+            style.text_color
+        } else {
+            style.inactive_color
+        };
+
+        ui.push_ui_element(
+            style,
+            vec2(width, height),
+            style.enabled.then_some(id),
+            Vec2::ZERO,
+            false,
+            false,
+            move |draw, cursor, size| {
+                let track_height = size.y / 4.0;
+                let track_cursor = cursor - vec2(0.0, (size.y - track_height) / 2.0);
+                let knob_x = knob_radius + t * travel;
+
+                draw.rectangle_rounded(
+                    track_cursor,
+                    vec2(size.x, track_height),
+                    track_height / 2.0,
+                    style.inactive_color,
+                );
+                draw.rectangle_rounded(
+                    track_cursor,
+                    vec2(knob_x, track_height),
+                    track_height / 2.0,
+                    foreground,
+                );
+
+                let knob_cursor =
+                    cursor + vec2(knob_x - knob_radius, -(size.y / 2.0 - knob_radius));
+                let thickness = knob_radius / 4.0;
+
+                draw.circle(knob_cursor, knob_radius / 2.0, knob_radius, foreground);
+                draw.circle(
+                    knob_cursor + vec2(thickness, -thickness),
+                    (knob_radius - thickness) / 2.0,
+                    knob_radius - thickness,
+                    knob_color,
+                );
+            },
+        );
+
+        let decimals = self.decimals.unwrap_or_else(|| auto_decimals(self.step));
+
+        let text_id = ui.current_id();
+        let text_response = ui.widget_response();
+        let text = format_value(value, decimals);
+
+        match value_edit(ui, text_id, &text, text_response.double_clicked) {
+            ValueEdit::Editing => {}
+            ValueEdit::Committed(committed) => {
+                value = clamp_range(snap(committed, self.step, min), start, end);
+                value_text(ui, format_value(value, decimals) + &self.suffix);
+            }
+            ValueEdit::Inactive => {
+                value_text(ui, text + &self.suffix);
+            }
+        }
+
+        if value != old_value {
+            *self.value = T::from_f64(value);
+            response.changed = true;
+        }
+
+        response
+    }
+}
+
+pub(crate) enum ValueEdit {
+    Inactive,
+    Editing,
+    Committed(f64),
+}
+
+/// Shows a `TextEdit` in place of a numeric value while the user is typing an
+/// exact number. Enter or clicking elsewhere commits, Escape cancels.
+pub(crate) fn value_edit(ui: &mut Ui, id: Id, text: &str, start: bool) -> ValueEdit {
+    if start {
+        let mut edit = TextEdit::new(text, 32);
+        edit.focus();
+        ui.state.value_edit = Some((id, edit));
+    }
+
+    let mut edit = match ui.state.value_edit.take() {
+        Some((edit_id, edit)) if edit_id == id => edit,
+        other => {
+            ui.state.value_edit = other;
+            return ValueEdit::Inactive;
+        }
+    };
+
+    if ui.window.is_key_pressed(Key::Escape, KeyRepeat::No) {
+        return ValueEdit::Inactive;
+    }
+
+    // The Enter that started the edit doesn't commit it
+    let enter = !start
+        && (ui.window.is_key_pressed(Key::Enter, KeyRepeat::No)
+            || ui.window.is_key_pressed(Key::NumPadEnter, KeyRepeat::No));
+
+    if enter || !edit.is_active() {
+        return match edit.string.trim().parse::<f64>() {
+            Ok(value) if value.is_finite() => ValueEdit::Committed(value),
+            _ => ValueEdit::Inactive,
+        };
+    }
+
+    let style = ui.style;
+    let font = style.font.unwrap_or_default();
+    let size = ui
+        .draw
+        .calc_text_size(&edit.string, style.text_height, f32::INFINITY, font)
+        + vec2(style.text_height, 0.0);
+    let size = vec2(size.x.max(4.0 * style.text_height), style.text_height);
+
+    let context = ui.draw.context();
+    let window = ui.window;
+
+    edit.show(size, window, context, ui);
+
+    ui.state.value_edit = Some((id, edit));
+
+    ValueEdit::Editing
+}
+
+fn value_text(ui: &mut Ui, text: String) {
+    let style = ui.style;
+    let font = style.font.unwrap_or_default();
+    let content_box = ui
+        .draw
+        .calc_text_size(&text, style.text_height, f32::INFINITY, font);
+
+    let color = if style.enabled {
+        style.text_color
+    } else {
+        style.inactive_color
+    };
+
+    ui.push_ui_element(
+        style,
+        content_box,
+        style.enabled.then(|| ui.current_id()),
+        Vec2::ZERO,
+        false,
+        false,
+        move |draw, cursor, content_box| {
+            draw.text(
+                &text,
+                cursor,
+                content_box,
+                style.text_height,
+                Align::Left,
+                color,
+                font,
+            );
+        },
+    );
+}
+
+/// Position of `value` on a track from `start` to `end`, 0 to 1.
+fn to_t(value: f64, start: f64, end: f64, logarithmic: bool) -> f64 {
+    if start == end {
+        0.0
+    } else if logarithmic {
+        ((value / start).ln() / (end / start).ln()).clamp(0.0, 1.0)
+    } else {
+        ((value - start) / (end - start)).clamp(0.0, 1.0)
+    }
+}
+
+fn from_t(t: f64, start: f64, end: f64, logarithmic: bool) -> f64 {
+    if logarithmic {
+        start * (end / start).powf(t)
+    } else {
+        start + t * (end - start)
+    }
+}
+
+/// Clamps between the ends of a range in either order, unlike `f64::clamp`
+/// this doesn't panic on a reversed range or a NaN end.
+#[inline]
+pub(crate) fn clamp_range(value: f64, start: f64, end: f64) -> f64 {
+    value.max(start.min(end)).min(start.max(end))
+}
+
+#[inline]
+pub(crate) fn snap(value: f64, step: Option<f64>, origin: f64) -> f64 {
+    match step {
+        Some(step) if step > 0.0 => origin + ((value - origin) / step).round() * step,
+        _ => value,
+    }
+}
+
+#[inline]
+pub(crate) fn auto_decimals(step: Option<f64>) -> usize {
+    match step {
+        Some(step) if step > 0.0 => (0..6)
+            .find(|decimals| {
+                let scaled = step * 10f64.powi(*decimals as i32);
+                (scaled - scaled.round()).abs() < 1e-6
+            })
+            .unwrap_or(6),
+        _ => 2,
+    }
+}
+
+#[inline]
+pub(crate) fn format_value(value: f64, decimals: usize) -> String {
+    format!("{value:.decimals$}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reversed_range() {
+        assert_eq!(to_t(10.0, 10.0, 0.0, false), 0.0);
+        assert_eq!(to_t(2.5, 10.0, 0.0, false), 0.75);
+        assert_eq!(to_t(-5.0, 10.0, 0.0, false), 1.0);
+        assert_eq!(from_t(0.25, 10.0, 0.0, false), 7.5);
+
+        assert!((to_t(10.0, 100.0, 1.0, true) - 0.5).abs() < 1e-9);
+        assert!((from_t(0.5, 100.0, 1.0, true) - 10.0).abs() < 1e-9);
+
+        assert_eq!(clamp_range(15.0, 10.0, 0.0), 10.0);
+        assert_eq!(clamp_range(-1.0, 10.0, 0.0), 0.0);
+        assert_eq!(clamp_range(4.0, 10.0, 0.0), 4.0);
+    }
+
+    #[test]
+    fn nan_range_end() {
+        assert_eq!(clamp_range(15.0, f64::NAN, 10.0), 10.0);
+        assert_eq!(clamp_range(15.0, 0.0, f64::NAN), 0.0);
+    }
+}
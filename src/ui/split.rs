@@ -0,0 +1,393 @@
+use crate::{
+    math::{vec2, Vec2},
+    ui::{
+        bounding_box::BoundingBox, frame, grid::container_style, id::Id, memory::Persist,
+        panel::DockArea, ui::Element, Ui,
+    },
+    window::CursorStyle,
+};
+use std::{cell::Cell, fmt, hash::Hash, rc::Rc, str::FromStr};
+
+const DIVIDER_WIDTH: f32 = 6.0;
+
+/// Two panes with a draggable divider between them.
+///
+/// The ratio the divider is dragged to is kept in `Memory` and included in
+/// `Memory::serialize`. Double clicking the divider collapses the smaller pane,
+/// double clicking it again brings the pane back.
+#[must_use = "You should call .show()"]
+pub struct Split {
+    id: Id,
+    horizontal: bool,
+    ratio: f32,
+    min_sizes: (f32, f32),
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+enum Collapsed {
+    #[default]
+    None,
+    First,
+    Second,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+struct SplitState {
+    ratio: Option<f32>,
+    collapsed: Collapsed,
+}
+
+impl Split {
+    /// Panes side by side, the first one on the left.
+    #[inline]
+    pub fn horizontal(id_source: impl Hash) -> Split {
+        Split::new(true, id_source)
+    }
+
+    /// Panes above each other, the first one on top.
+    #[inline]
+    pub fn vertical(id_source: impl Hash) -> Split {
+        Split::new(false, id_source)
+    }
+
+    fn new(horizontal: bool, id_source: impl Hash) -> Split {
+        Split {
+            id: Id::new(id_source),
+            horizontal,
+            ratio: 0.5,
+            min_sizes: (0.0, 0.0),
+        }
+    }
+
+    /// Share of the space the first pane starts out with.
+    #[inline]
+    pub fn ratio(mut self, ratio: f32) -> Split {
+        self.ratio = ratio.clamp(0.0, 1.0);
+        self
+    }
+
+    /// The divider can't be dragged closer than this to either edge.
+    #[inline]
+    pub fn min_sizes(mut self, first: f32, second: f32) -> Split {
+        self.min_sizes = (first, second);
+        self
+    }
+
+    pub fn show(self, ui: &mut Ui, first: impl FnOnce(&mut Ui), second: impl FnOnce(&mut Ui)) {
+        let style = ui.style;
+        let id = ui.parent_id.with_child(self.id);
+        let divider_id = id.with_child(Id::new("divider"));
+
+        let length = ui
+            .state
+            .splits
+            .entry(id)
+            .or_insert_with(|| Rc::new(Cell::new(0.0)))
+            .clone();
+
+        let mut split = *ui.memory.get_persisted_or_default::<SplitState>(id);
+
+        let response = ui.response_for(divider_id);
+
+        if response.double_clicked {
+            split.toggle_collapsed(self.ratio);
+        }
+
+        if response.dragged && length.get() > 0.0 {
+            let delta = if self.horizontal {
+                response.drag_delta.x
+            } else {
+                -response.drag_delta.y
+            };
+
+            split.drag(self.ratio, delta, length.get(), self.min_sizes);
+        }
+
+        if response.double_clicked || response.dragged {
+            ui.memory.insert(id, split);
+        }
+
+        if response.hovered || response.dragged {
+            ui.set_cursor(if self.horizontal {
+                CursorStyle::ResizeLeftRight
+            } else {
+                CursorStyle::ResizeUpDown
+            });
+        }
+
+        let first = DockArea::new(pane(ui, id.with_child(Id::new(0)), first));
+        let second = DockArea::new(pane(ui, id.with_child(Id::new(1)), second));
+
+        let min_length = self.min_sizes.0 + self.min_sizes.1 + DIVIDER_WIDTH;
+        let content_box = if self.horizontal {
+            vec2(min_length, first.min_size().y.max(second.min_size().y))
+        } else {
+            vec2(first.min_size().x.max(second.min_size().x), min_length)
+        };
+
+        let horizontal = self.horizontal;
+        let min_sizes = self.min_sizes;
+        let default_ratio = self.ratio;
+        let active = response.hovered || response.dragged;
+
+        ui.current_line.push(Element {
+            content_box: Rc::new(Cell::new(content_box)),
+            id: None,
+            border_extra: Vec2::ZERO,
+            flex_x: true,
+            flex_y: false,
+            weight: vec2(1.0, 1.0),
+            max_size: Vec2::INFINITY,
+            style: container_style(style),
+            render: Some(Box::new(move |draw, cursor, size| {
+                // The panes draw both passes themselves, like the lines of a frame
+                if draw.pass() != 1 {
+                    return;
+                }
+
+                let available = if horizontal { size.x } else { size.y } - DIVIDER_WIDTH;
+                let available = available.max(0.0);
+
+                length.set(available);
+
+                let first_length = split.first_length(default_ratio, available, min_sizes);
+
+                let second_length = available - first_length;
+
+                let (first_size, divider, second_size) = if horizontal {
+                    (
+                        vec2(first_length, size.y),
+                        BoundingBox::new(
+                            cursor + vec2(first_length, 0.0),
+                            vec2(DIVIDER_WIDTH, size.y),
+                        ),
+                        vec2(second_length, size.y),
+                    )
+                } else {
+                    (
+                        vec2(size.x, first_length),
+                        BoundingBox::new(
+                            cursor - vec2(0.0, first_length),
+                            vec2(size.x, DIVIDER_WIDTH),
+                        ),
+                        vec2(size.x, second_length),
+                    )
+                };
+
+                let second_cursor = if horizontal {
+                    divider.top_left + vec2(DIVIDER_WIDTH, 0.0)
+                } else {
+                    divider.top_left - vec2(0.0, DIVIDER_WIDTH)
+                };
+
+                let area_style = container_style(style);
+
+                first.render(draw, cursor, first_size, &area_style);
+                second.render(draw, second_cursor, second_size, &area_style);
+
+                let color = if active {
+                    style.focus_color
+                } else {
+                    style.border_color
+                };
+
+                let center = divider.top_left + vec2(divider.size.x, -divider.size.y) / 2.0;
+
+                if horizontal {
+                    draw.line(
+                        vec2(center.x, divider.top_left.y),
+                        vec2(center.x, divider.top_left.y - divider.size.y),
+                        if active { 2.0 } else { 1.0 },
+                        color,
+                    );
+                } else {
+                    draw.line(
+                        vec2(divider.top_left.x, center.y),
+                        vec2(divider.top_left.x + divider.size.x, center.y),
+                        if active { 2.0 } else { 1.0 },
+                        color,
+                    );
+                }
+
+                draw.push_box(divider_id, divider);
+            })),
+            update_with_max_width: None,
+        });
+    }
+}
+
+impl SplitState {
+    /// Collapses the smaller pane, or brings back the collapsed one.
+    fn toggle_collapsed(&mut self, default_ratio: f32) {
+        let ratio = self.ratio.unwrap_or(default_ratio);
+
+        self.collapsed = match self.collapsed {
+            Collapsed::None if ratio <= 0.5 => Collapsed::First,
+            Collapsed::None => Collapsed::Second,
+            _ => Collapsed::None,
+        };
+        self.ratio = Some(ratio);
+    }
+
+    /// Moves the divider by `delta` out of a `length` long split, keeping both panes
+    /// at least their `min_sizes` long.
+    fn drag(&mut self, default_ratio: f32, delta: f32, length: f32, min_sizes: (f32, f32)) {
+        // Dragging a collapsed pane pulls it out from its edge
+        let ratio = match self.collapsed {
+            Collapsed::None => self.ratio.unwrap_or(default_ratio),
+            Collapsed::First => 0.0,
+            Collapsed::Second => 1.0,
+        };
+
+        let min = min_sizes.0 / length;
+        let max = 1.0 - min_sizes.1 / length;
+
+        self.ratio = Some((ratio + delta / length).min(max).max(min));
+        self.collapsed = Collapsed::None;
+    }
+
+    /// Length of the first pane when the panes share `available`.
+    fn first_length(&self, default_ratio: f32, available: f32, min_sizes: (f32, f32)) -> f32 {
+        match self.collapsed {
+            Collapsed::First => 0.0,
+            Collapsed::Second => available,
+            Collapsed::None => (available * self.ratio.unwrap_or(default_ratio))
+                .min(available - min_sizes.1)
+                .max(min_sizes.0)
+                .min(available),
+        }
+    }
+}
+
+fn pane<'a>(ui: &mut Ui<'a, '_, '_>, id: Id, func: impl FnOnce(&mut Ui)) -> Element<'a> {
+    let style = ui.style;
+
+    frame::build(
+        ui,
+        true,
+        container_style(style),
+        None,
+        None,
+        id,
+        true,
+        |ui| {
+            ui.style = style;
+            func(ui);
+        },
+    )
+}
+
+impl Persist for SplitState {
+    const TAG: &'static str = "split";
+}
+
+impl fmt::Display for SplitState {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.ratio {
+            Some(ratio) => write!(f, "{}", ratio)?,
+            None => write!(f, "-")?,
+        }
+
+        match self.collapsed {
+            Collapsed::None => Ok(()),
+            Collapsed::First => write!(f, " first"),
+            Collapsed::Second => write!(f, " second"),
+        }
+    }
+}
+
+impl FromStr for SplitState {
+    type Err = ();
+
+    fn from_str(text: &str) -> Result<SplitState, ()> {
+        let mut parts = text.split_whitespace();
+
+        let ratio = match parts.next() {
+            Some("-") => None,
+            Some(ratio) => Some(ratio.parse().map_err(|_| ())?),
+            None => return Err(()),
+        };
+
+        let collapsed = match parts.next() {
+            None => Collapsed::None,
+            Some("first") => Collapsed::First,
+            Some("second") => Collapsed::Second,
+            Some(_) => return Err(()),
+        };
+
+        Ok(SplitState { ratio, collapsed })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn split_state_round_trip() {
+        for state in [
+            SplitState::default(),
+            SplitState {
+                ratio: Some(0.3),
+                collapsed: Collapsed::None,
+            },
+            SplitState {
+                ratio: Some(0.25),
+                collapsed: Collapsed::First,
+            },
+            SplitState {
+                ratio: None,
+                collapsed: Collapsed::Second,
+            },
+        ] {
+            assert_eq!(state.to_string().parse::<SplitState>(), Ok(state));
+        }
+    }
+
+    #[test]
+    fn drag_keeps_the_min_sizes() {
+        let mut split = SplitState::default();
+
+        split.drag(0.5, -500.0, 200.0, (50.0, 25.0));
+        assert_eq!(split.ratio, Some(0.25));
+        assert_eq!(split.first_length(0.5, 200.0, (50.0, 25.0)), 50.0);
+
+        split.drag(0.5, 500.0, 200.0, (50.0, 25.0));
+        assert_eq!(split.ratio, Some(0.875));
+        assert_eq!(split.first_length(0.5, 200.0, (50.0, 25.0)), 175.0);
+
+        // A smaller window can't push the panes past their minimum either
+        assert_eq!(split.first_length(0.5, 100.0, (50.0, 25.0)), 75.0);
+    }
+
+    #[test]
+    fn double_click_toggles_collapse() {
+        let mut split = SplitState::default();
+
+        split.toggle_collapsed(0.25);
+        assert_eq!(split.collapsed, Collapsed::First);
+        assert_eq!(split.first_length(0.25, 200.0, (50.0, 25.0)), 0.0);
+
+        split.toggle_collapsed(0.25);
+        assert_eq!(split.collapsed, Collapsed::None);
+        assert_eq!(split.first_length(0.25, 200.0, (50.0, 25.0)), 50.0);
+
+        let mut split = SplitState::default();
+
+        split.toggle_collapsed(0.75);
+        assert_eq!(split.collapsed, Collapsed::Second);
+        assert_eq!(split.first_length(0.75, 200.0, (50.0, 25.0)), 200.0);
+    }
+
+    #[test]
+    fn drag_pulls_out_a_collapsed_pane() {
+        let mut split = SplitState {
+            ratio: Some(0.5),
+            collapsed: Collapsed::First,
+        };
+
+        split.drag(0.5, 40.0, 200.0, (0.0, 0.0));
+        assert_eq!(split.collapsed, Collapsed::None);
+        assert_eq!(split.ratio, Some(0.2));
+    }
+}
@@ -6,6 +6,7 @@ use crate::{
 pub const LIGHT_GRAY: Vec4 = vec4(0.8, 0.8, 0.8, 1.0);
 pub const DARK_GRAY: Vec4 = vec4(0.2, 0.2, 0.2, 0.5);
 pub const MEDIUM_GRAY: Vec4 = vec4(0.4, 0.4, 0.4, 1.0);
+pub const LIGHT_BLUE: Vec4 = vec4(0.4, 0.6, 1.0, 1.0);
 
 #[derive(Clone, Copy, Debug)]
 pub struct Style {
@@ -24,6 +25,11 @@ pub struct Style {
     pub shadow_dir: Option<Vec2>,
     pub shadow_scale: f32,
     pub shadow_color: Vec4,
+    pub enabled: bool,
+    pub focus_color: Vec4,
+    pub focus_width: f32,
+    pub tooltip_delay: f32,
+    pub animation_time: f32,
     pub debug: bool,
 }
 
@@ -47,6 +53,11 @@ impl Default for Style {
             shadow_dir: None,
             shadow_scale: 1.0,
             shadow_color: vec4(0.1, 0.1, 0.1, 0.9),
+            enabled: true,
+            focus_color: LIGHT_BLUE,
+            focus_width: 2.0,
+            tooltip_delay: 0.5,
+            animation_time: 0.0,
             debug: false,
         }
     }
@@ -190,6 +201,49 @@ impl Style {
         res
     }
 
+    #[must_use]
+    #[inline]
+    pub fn enabled(&self, enabled: bool) -> Style {
+        let mut res = *self;
+        res.enabled = enabled;
+        res
+    }
+
+    /// Color of the ring drawn around the widget focused with Tab.
+    #[must_use]
+    #[inline]
+    pub fn focus_color(&self, focus_color: Vec4) -> Style {
+        let mut res = *self;
+        res.focus_color = focus_color;
+        res
+    }
+
+    #[must_use]
+    #[inline]
+    pub fn focus_width(&self, focus_width: f32) -> Style {
+        let mut res = *self;
+        res.focus_width = focus_width;
+        res
+    }
+
+    /// Seconds the pointer has to rest on a widget before its tooltip is shown.
+    #[must_use]
+    #[inline]
+    pub fn tooltip_delay(&self, tooltip_delay: f32) -> Style {
+        let mut res = *self;
+        res.tooltip_delay = tooltip_delay;
+        res
+    }
+
+    /// Seconds hover and press colors take to fade in and out, 0 switches instantly.
+    #[must_use]
+    #[inline]
+    pub fn animation_time(&self, animation_time: f32) -> Style {
+        let mut res = *self;
+        res.animation_time = animation_time;
+        res
+    }
+
     #[must_use]
     #[inline]
     pub fn debug(&self, debug: bool) -> Style {
@@ -0,0 +1,610 @@
+use crate::{
+    math::{vec2, Vec2},
+    ui::{
+        bounding_box::BoundingBox,
+        color::{held_color, hover_color},
+        draw_api::DrawApi,
+        frame,
+        id::Id,
+        memory::Persist,
+        scroll_area::{self, Scrollbar, MIN_THUMB_LENGTH, SCROLLBAR_WIDTH, SCROLL_LINES_PER_STEP},
+        spacing,
+        ui::{Element, FLEX_WEIGHT},
+        Spacing, Style, Ui,
+    },
+};
+use std::{
+    cell::{Cell, RefCell},
+    cmp::Ordering,
+    fmt,
+    hash::Hash,
+    rc::Rc,
+    str::FromStr,
+};
+
+const RESIZE_HANDLE_WIDTH: f32 = 6.0;
+
+#[derive(Clone, Copy, Debug)]
+pub enum Column {
+    /// Starts at the given width, can be resized by dragging the header border.
+    Fixed(f32),
+    /// Fits its content and shares the remaining width with other flexible columns.
+    Flex,
+}
+
+#[derive(Clone, Copy, Default)]
+pub struct TableResponse {
+    /// Row index, as passed to the cell closure, of the selected row.
+    pub selected: Option<usize>,
+    pub selection_changed: bool,
+    pub sort_column: Option<usize>,
+    pub ascending: bool,
+}
+
+#[derive(Clone, Default)]
+pub(crate) struct TableState {
+    widths: Vec<Option<f32>>,
+    sort: Option<(usize, bool)>,
+    selected: Option<usize>,
+    offset: f32,
+    drag_start_offset: f32,
+    rendered_widths: Rc<RefCell<Vec<f32>>>,
+}
+
+type Compare<'t> = dyn Fn(usize, usize, usize) -> Ordering + 't;
+
+/// Column widths, the sort order, the selected row and the scroll offset are
+/// kept in `Memory` and included in `Memory::serialize`.
+#[must_use = "You should call .show()"]
+pub struct Table<'t> {
+    id: Id,
+    columns: Vec<(String, Column)>,
+    striped: bool,
+    height: Option<f32>,
+    compare: Option<Box<Compare<'t>>>,
+}
+
+impl<'t> Table<'t> {
+    #[inline]
+    pub fn new(id_source: impl Hash) -> Table<'t> {
+        Table {
+            id: Id::new(id_source),
+            columns: Vec::new(),
+            striped: false,
+            height: None,
+            compare: None,
+        }
+    }
+
+    #[inline]
+    pub fn column(mut self, header: impl Into<String>, column: Column) -> Table<'t> {
+        self.columns.push((header.into(), column));
+        self
+    }
+
+    #[inline]
+    pub fn striped(mut self, striped: bool) -> Table<'t> {
+        self.striped = striped;
+        self
+    }
+
+    /// Total height including the header, rows beyond it scroll below the header.
+    #[inline]
+    pub fn height(mut self, height: f32) -> Table<'t> {
+        self.height = Some(height);
+        self
+    }
+
+    /// Makes the headers clickable, `compare(a, b, column)` orders two rows by a column.
+    #[inline]
+    pub fn sort_by(mut self, compare: impl Fn(usize, usize, usize) -> Ordering + 't) -> Table<'t> {
+        self.compare = Some(Box::new(compare));
+        self
+    }
+
+    pub fn show(
+        self,
+        ui: &mut Ui,
+        row_count: usize,
+        mut cell: impl FnMut(&mut Ui, usize, usize),
+    ) -> TableResponse {
+        let style = ui.style;
+        let id = ui.parent_id.with_child(self.id);
+        let column_count = self.columns.len();
+
+        let mut table = ui.memory.get_persisted_or_default::<TableState>(id).clone();
+        table.widths.resize(column_count, None);
+
+        // Restored state can refer to columns or rows the table no longer has
+        table.sort = table.sort.filter(|(column, _)| *column < column_count);
+        table.selected = table.selected.filter(|row| *row < row_count);
+
+        let column_id =
+            move |column: usize, kind: f32| id.with_child(Id::from_vec2(vec2(column as f32, kind)));
+        let row_id = move |row: usize| id.with_child(Id::from_vec2(vec2(-1.0, row as f32)));
+        let thumb_id = id.with_child(Id::new("thumb"));
+
+        let mut response = TableResponse::default();
+
+        for column in 0..column_count {
+            let handle_id = column_id(column, -2.0);
+
+            if ui.response_for(handle_id).pressed {
+                let width = table
+                    .rendered_widths
+                    .borrow()
+                    .get(column)
+                    .copied()
+                    .unwrap_or(style.text_height);
+                ui.state.drag_start_value = width as f64;
+            }
+
+            if ui.is_dragging(handle_id) {
+                let delta = ui.state.mouse_pos.x - ui.state.mouse_pressed_pos.x;
+                let width = ui.state.drag_start_value as f32 + delta;
+                table.widths[column] = Some(width.max(style.text_height));
+            }
+
+            if self.compare.is_some() && ui.widget_response_for(column_id(column, -1.0)).pressed {
+                table.sort = match table.sort {
+                    Some((sorted, ascending)) if sorted == column => Some((column, !ascending)),
+                    _ => Some((column, true)),
+                };
+            }
+        }
+
+        let mut order = (0..row_count).collect::<Vec<_>>();
+
+        if let (Some(compare), Some((column, ascending))) = (&self.compare, table.sort) {
+            order.sort_by(|a, b| {
+                let ordering = compare(*a, *b, column);
+                if ascending {
+                    ordering
+                } else {
+                    ordering.reverse()
+                }
+            });
+        }
+
+        for &row in &order {
+            if ui.widget_response_for(row_id(row)).pressed {
+                response.selection_changed = table.selected != Some(row);
+                table.selected = Some(row);
+            }
+        }
+
+        let headers = self
+            .columns
+            .iter()
+            .enumerate()
+            .map(|(column, (header, _))| {
+                let header = header.clone();
+                frame::build(
+                    ui,
+                    true,
+                    style,
+                    None,
+                    None,
+                    column_id(column, -3.0),
+                    false,
+                    |ui| ui.text(header),
+                )
+            })
+            .collect::<Vec<_>>();
+
+        let rows = order
+            .iter()
+            .map(|&row| {
+                (0..column_count)
+                    .map(|column| {
+                        let parent_id =
+                            id.with_child(Id::from_vec2(vec2(column as f32, row as f32)));
+                        frame::build(ui, true, style, None, None, parent_id, false, |ui| {
+                            cell(ui, row, column)
+                        })
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+
+        let cell_size = |element: &Element| {
+            spacing::bounding_box(
+                element.content_box.get(),
+                element.style.margin,
+                element.style.padding,
+            )
+        };
+
+        let sort_indicator = if self.compare.is_some() {
+            style.text_height
+        } else {
+            0.0
+        };
+
+        let min_widths = self
+            .columns
+            .iter()
+            .enumerate()
+            .map(|(column, (_, kind))| match (table.widths[column], *kind) {
+                (Some(width), _) | (None, Column::Fixed(width)) => width,
+                (None, Column::Flex) => rows
+                    .iter()
+                    .map(|cells| cell_size(&cells[column]).width())
+                    .fold(
+                        cell_size(&headers[column]).width() + sort_indicator,
+                        f32::max,
+                    ),
+            })
+            .collect::<Vec<_>>();
+
+        let flex = self
+            .columns
+            .iter()
+            .enumerate()
+            .map(|(column, (_, kind))| {
+                matches!(kind, Column::Flex) && table.widths[column].is_none()
+            })
+            .collect::<Vec<_>>();
+
+        let header_height = headers
+            .iter()
+            .map(|header| cell_size(header).height())
+            .fold(0.0, f32::max);
+
+        let row_heights = rows
+            .iter()
+            .map(|cells| {
+                cells
+                    .iter()
+                    .map(|cell| cell_size(cell).height())
+                    .fold(0.0, f32::max)
+            })
+            .collect::<Vec<_>>();
+
+        let body_height = row_heights.iter().sum::<f32>();
+        let viewport_height = self
+            .height
+            .map(|height| (height - header_height).max(0.0))
+            .unwrap_or(body_height);
+        let max_offset = (body_height - viewport_height).max(0.0);
+        let bar_width = if max_offset > 0.0 {
+            SCROLLBAR_WIDTH
+        } else {
+            0.0
+        };
+
+        let thumb_length = (viewport_height * viewport_height / body_height.max(1.0))
+            .max(MIN_THUMB_LENGTH)
+            .min(viewport_height);
+        let travel = viewport_height - thumb_length;
+
+        let thumb = ui.response_for(thumb_id);
+        let thumb_held = ui.is_dragging(thumb_id);
+
+        if thumb.pressed {
+            table.drag_start_offset = table.offset;
+        }
+
+        if thumb_held && travel > 0.0 {
+            let delta = ui.state.mouse_pos.y - ui.state.mouse_pressed_pos.y;
+            table.offset = table.drag_start_offset - delta * max_offset / travel;
+        }
+
+        if ui.response_for(id).hovered && max_offset > 0.0 && ui.state.scroll_wheel.y != 0.0 {
+            table.offset -= ui.state.scroll_wheel.y * style.text_height * SCROLL_LINES_PER_STEP;
+            ui.state.scroll_wheel.y = 0.0;
+        }
+
+        table.offset = table.offset.clamp(0.0, max_offset);
+
+        response.selected = table.selected;
+        response.sort_column = table.sort.map(|(column, _)| column);
+        response.ascending = !matches!(table.sort, Some((_, false)));
+
+        let offset = table.offset;
+        let selected = table
+            .selected
+            .and_then(|selected| order.iter().position(|row| *row == selected));
+        let sort = table.sort;
+        let sortable = self.compare.is_some();
+        let striped = self.striped;
+
+        let scrollbar = Scrollbar {
+            visible: max_offset > 0.0,
+            thumb_pos: offset / max_offset.max(1.0) * travel,
+            thumb_length,
+            thumb_hovered: thumb.hovered,
+            thumb_held,
+        };
+
+        let header_hovered = (0..column_count)
+            .map(|column| sortable && ui.response_for(column_id(column, -1.0)).hovered)
+            .collect::<Vec<_>>();
+
+        let rendered_widths = table.rendered_widths.clone();
+
+        ui.memory.insert(id, table);
+
+        let min_width = min_widths.iter().sum::<f32>();
+        let table_style = style.margin(Spacing::ZERO).padding(Spacing::ZERO);
+
+        ui.current_line.push(Element {
+            content_box: Rc::new(Cell::new(vec2(
+                min_width + bar_width,
+                header_height + viewport_height,
+            ))),
+            id: Some(id),
+            border_extra: Vec2::ZERO,
+            flex_x: flex.iter().any(|flex| *flex),
+            flex_y: false,
+            weight: FLEX_WEIGHT,
+            max_size: Vec2::INFINITY,
+            style: table_style,
+            render: Some(Box::new(move |draw, cursor, size| {
+                if draw.pass() != 1 {
+                    return;
+                }
+
+                let flex_count = flex.iter().filter(|flex| **flex).count().max(1) as f32;
+                let extra = (size.x - bar_width - min_width).max(0.0) / flex_count;
+
+                let widths = min_widths
+                    .iter()
+                    .zip(&flex)
+                    .map(|(width, flex)| if *flex { width + extra } else { *width })
+                    .collect::<Vec<_>>();
+
+                rendered_widths.replace(widths.clone());
+
+                let render_cell =
+                    |draw: &mut DrawApi, element: &Element, cell_cursor: Vec2, cell_size: Vec2| {
+                        let margin = element.style.margin;
+                        let padding = element.style.padding;
+
+                        let cursor = cell_cursor
+                            + vec2(margin.left + padding.left, -margin.top - padding.top);
+                        let size = (cell_size - (margin + padding).size()).max(Vec2::ZERO);
+
+                        if let Some(render) = element.render.as_ref() {
+                            render(draw, cursor, size);
+                        }
+                    };
+
+                let inner_width = size.x - bar_width;
+                let body_cursor = cursor - vec2(0.0, header_height);
+
+                draw.push_clip(body_cursor, vec2(inner_width, viewport_height));
+
+                let mut y = body_cursor.y + offset;
+
+                for (index, (cells, row_height)) in rows.iter().zip(&row_heights).enumerate() {
+                    let row_cursor = vec2(cursor.x, y);
+                    let row_size = vec2(inner_width, *row_height);
+
+                    y -= row_height;
+
+                    if row_cursor.y - row_size.y > body_cursor.y
+                        || row_cursor.y < body_cursor.y - viewport_height
+                    {
+                        continue;
+                    }
+
+                    let color = if selected == Some(index) {
+                        held_color(style.frame_color)
+                    } else if striped && index % 2 == 1 {
+                        hover_color(style.frame_color)
+                    } else {
+                        style.frame_color
+                    };
+
+                    draw.rectangle(row_cursor, row_size, color);
+                    draw.push_box(row_id(order[index]), BoundingBox::new(row_cursor, row_size));
+
+                    let mut x = cursor.x;
+
+                    for (element, width) in cells.iter().zip(&widths) {
+                        render_cell(
+                            draw,
+                            element,
+                            vec2(x, row_cursor.y),
+                            vec2(*width, *row_height),
+                        );
+                        x += width;
+                    }
+                }
+
+                draw.pop_clip();
+
+                let mut x = cursor.x;
+
+                for (column, (header, width)) in headers.iter().zip(&widths).enumerate() {
+                    let header_cursor = vec2(x, cursor.y);
+                    let header_size = vec2(*width, header_height);
+
+                    let color = if header_hovered[column] {
+                        hover_color(held_color(style.frame_color))
+                    } else {
+                        held_color(style.frame_color)
+                    };
+
+                    draw.rectangle(header_cursor, header_size, color);
+
+                    if sortable {
+                        draw.push_box(
+                            column_id(column, -1.0),
+                            BoundingBox::new(header_cursor, header_size),
+                        );
+                    }
+
+                    render_cell(
+                        draw,
+                        header,
+                        header_cursor,
+                        vec2(width - sort_indicator, header_height),
+                    );
+
+                    if let Some((_, ascending)) = sort.filter(|(sorted, _)| *sorted == column) {
+                        draw_sort_indicator(
+                            draw,
+                            header_cursor + vec2(width - sort_indicator, 0.0),
+                            vec2(sort_indicator, header_height),
+                            ascending,
+                            &style,
+                        );
+                    }
+
+                    x += width;
+
+                    draw.line(
+                        vec2(x, cursor.y),
+                        vec2(x, cursor.y - header_height),
+                        1.0,
+                        style.border_color,
+                    );
+                }
+
+                draw.rectangle(
+                    vec2(cursor.x + inner_width, cursor.y),
+                    vec2(bar_width, header_height),
+                    held_color(style.frame_color),
+                );
+
+                let mut x = cursor.x;
+
+                for (column, width) in widths.iter().enumerate() {
+                    x += width;
+
+                    draw.push_box(
+                        column_id(column, -2.0),
+                        BoundingBox::new(
+                            vec2(x - RESIZE_HANDLE_WIDTH / 2.0, cursor.y),
+                            vec2(RESIZE_HANDLE_WIDTH, header_height),
+                        ),
+                    );
+                }
+
+                if scrollbar.visible {
+                    let track_cursor = body_cursor + vec2(inner_width, 0.0);
+                    let thumb_cursor = track_cursor - vec2(0.0, scrollbar.thumb_pos);
+                    let thumb_size = vec2(SCROLLBAR_WIDTH, scrollbar.thumb_length);
+
+                    scroll_area::draw_scrollbar(
+                        draw,
+                        &style,
+                        track_cursor,
+                        vec2(SCROLLBAR_WIDTH, viewport_height),
+                        thumb_cursor,
+                        thumb_size,
+                        &scrollbar,
+                    );
+
+                    draw.push_box(thumb_id, BoundingBox::new(thumb_cursor, thumb_size));
+                }
+            })),
+            update_with_max_width: None,
+        });
+
+        response
+    }
+}
+
+fn draw_sort_indicator(
+    draw: &mut DrawApi,
+    cursor: Vec2,
+    size: Vec2,
+    ascending: bool,
+    style: &Style,
+) {
+    let extent = size.x.min(size.y) * 0.4;
+    let center = cursor + vec2(size.x / 2.0, -size.y / 2.0);
+    let width = extent / 5.0;
+    let tip = if ascending {
+        extent / 4.0
+    } else {
+        -extent / 4.0
+    };
+
+    draw.line(
+        center + vec2(-extent / 2.0, -tip),
+        center + vec2(0.0, tip),
+        width,
+        style.text_color,
+    );
+    draw.line(
+        center + vec2(0.0, tip),
+        center + vec2(extent / 2.0, -tip),
+        width,
+        style.text_color,
+    );
+}
+
+impl Persist for TableState {
+    const TAG: &'static str = "table";
+}
+
+impl fmt::Display for TableState {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.offset)?;
+
+        match self.sort {
+            Some((column, ascending)) => write!(f, " {} {}", column, ascending)?,
+            None => write!(f, " - -")?,
+        }
+
+        match self.selected {
+            Some(row) => write!(f, " {}", row)?,
+            None => write!(f, " -")?,
+        }
+
+        for width in &self.widths {
+            match width {
+                Some(width) => write!(f, " {}", width)?,
+                None => write!(f, " -")?,
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl FromStr for TableState {
+    type Err = ();
+
+    fn from_str(text: &str) -> Result<TableState, ()> {
+        let parts = text.split_whitespace().collect::<Vec<_>>();
+
+        let [offset, sort_column, ascending, selected, ref widths @ ..] = parts[..] else {
+            return Err(());
+        };
+
+        let sort = match (sort_column, ascending) {
+            ("-", "-") => None,
+            _ => Some((
+                sort_column.parse().map_err(|_| ())?,
+                ascending.parse().map_err(|_| ())?,
+            )),
+        };
+
+        let selected = match selected {
+            "-" => None,
+            _ => Some(selected.parse().map_err(|_| ())?),
+        };
+
+        let widths = widths
+            .iter()
+            .map(|width| match *width {
+                "-" => Ok(None),
+                _ => width.parse().map(Some).map_err(|_| ()),
+            })
+            .collect::<Result<_, ()>>()?;
+
+        Ok(TableState {
+            widths,
+            sort,
+            selected,
+            offset: offset.parse().map_err(|_| ())?,
+            drag_start_offset: 0.0,
+            rendered_widths: Rc::default(),
+        })
+    }
+}
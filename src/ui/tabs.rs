@@ -0,0 +1,237 @@
+use crate::{
+    math::{vec2, Vec2},
+    ui::{
+        color::{held_color, hover_color},
+        frame,
+        id::Id,
+        memory::Persist,
+        scroll_area::SCROLL_LINES_PER_STEP,
+        spacing,
+        ui::{Element, FLEX_WEIGHT},
+        Align, FrameStyle, Response, Spacing, Style, Ui,
+    },
+};
+use std::{cell::Cell, fmt, hash::Hash, rc::Rc, str::FromStr};
+
+#[derive(Clone, Default)]
+pub(crate) struct TabsState {
+    offset: f32,
+    viewport_width: Rc<Cell<f32>>,
+    /// The tab the strip last scrolled to.
+    scrolled_to: Option<usize>,
+}
+
+pub(crate) fn show(
+    ui: &mut Ui,
+    id_source: impl Hash,
+    selected: &mut usize,
+    labels: &[impl AsRef<str>],
+    func: impl FnOnce(&mut Ui, usize),
+) -> Response {
+    let style = ui.style;
+    let id = ui.parent_id.with_child(Id::new(id_source));
+    let area_style = style.margin(Spacing::ZERO).padding(Spacing::ZERO);
+
+    let mut response = Response::default();
+
+    frame::show(ui, true, area_style, None, None, true, |ui| {
+        response = build(ui, style, id, selected, labels, func);
+    });
+
+    response
+}
+
+fn build(
+    ui: &mut Ui,
+    style: Style,
+    id: Id,
+    selected: &mut usize,
+    labels: &[impl AsRef<str>],
+    func: impl FnOnce(&mut Ui, usize),
+) -> Response {
+    let strip_id = id.with_child(Id::new("strip"));
+
+    let mut tabs = ui.memory.get_persisted_or_default::<TabsState>(id).clone();
+
+    // The caller owns the selection, a removed tab can leave it out of range
+    let mut selected_tab = (*selected).min(labels.len().saturating_sub(1));
+
+    let mut response = ui.response_for(strip_id);
+
+    let mut strip_ui = Ui {
+        draw: ui.draw,
+        window: ui.window,
+        responses: ui.responses,
+        state: ui.state,
+        memory: ui.memory,
+        overlays: ui.overlays,
+        style,
+        current_line: Vec::new(),
+        lines: Vec::new(),
+        parent_id: strip_id,
+        id_origin: (0, 0),
+        next_id: None,
+        panels: Vec::new(),
+    };
+
+    for (index, label) in labels.iter().enumerate() {
+        let active = index == selected_tab;
+        let tab = strip_ui.widget_response();
+
+        if tab.pressed && !active {
+            selected_tab = index;
+            response.changed = true;
+        }
+
+        let tab_style = if tab.held {
+            style.frame_color(held_color(style.frame_color))
+        } else if tab.hovered && !active {
+            style.frame_color(hover_color(style.frame_color))
+        } else if active {
+            style
+        } else {
+            style.frame_style(FrameStyle::None)
+        };
+
+        let label = label.as_ref().to_owned();
+        let tab_id = strip_ui.current_id();
+
+        frame::show(
+            &mut strip_ui,
+            false,
+            tab_style,
+            None,
+            style.enabled.then_some(tab_id),
+            false,
+            move |ui| ui.active_text(label, active),
+        );
+    }
+
+    let (lines, line_sizes, content_size, _) = strip_ui.layout();
+
+    let widths = lines[0]
+        .iter()
+        .map(|tab| {
+            spacing::bounding_box(tab.content_box.get(), tab.style.margin, tab.style.padding)
+                .width()
+        })
+        .collect::<Vec<_>>();
+
+    let min_width = widths.get(selected_tab).copied().unwrap_or_default();
+
+    let viewport_width = tabs.viewport_width.get();
+    let max_offset = (content_size.x - viewport_width).max(0.0);
+
+    // A newly selected tab is scrolled into view, whether it was clicked or
+    // picked by the caller, once the width of the strip is known
+    if !labels.is_empty() && viewport_width > 0.0 && tabs.scrolled_to != Some(selected_tab) {
+        let left = widths[..selected_tab].iter().sum::<f32>();
+
+        tabs.offset = tabs.offset.min(left).max(left + min_width - viewport_width);
+        tabs.scrolled_to = Some(selected_tab);
+    }
+
+    if response.hovered {
+        let wheel = ui.state.scroll_wheel;
+        let wheel = if wheel.x != 0.0 { wheel.x } else { wheel.y };
+
+        if max_offset > 0.0 && wheel != 0.0 {
+            tabs.offset -= wheel * style.text_height * SCROLL_LINES_PER_STEP;
+            ui.state.scroll_wheel = Vec2::ZERO;
+        }
+    }
+
+    tabs.offset = tabs.offset.clamp(0.0, max_offset);
+
+    let offset = tabs.offset;
+    let viewport_width = tabs.viewport_width.clone();
+
+    ui.memory.insert(id, tabs);
+
+    if !labels.is_empty() {
+        *selected = selected_tab;
+    }
+
+    let strip_style = style
+        .align(Align::Left)
+        .margin(Spacing::ZERO)
+        .padding(Spacing::ZERO);
+
+    let strip = Element {
+        content_box: Rc::new(Cell::new(vec2(min_width, content_size.y))),
+        id: Some(strip_id),
+        border_extra: Vec2::ZERO,
+        flex_x: true,
+        flex_y: false,
+        weight: FLEX_WEIGHT,
+        max_size: Vec2::INFINITY,
+        style: strip_style,
+        render: Some(Box::new(move |draw, cursor, size| {
+            if draw.pass() != 1 {
+                return;
+            }
+
+            viewport_width.set(size.x);
+
+            let offset = offset.min((content_size.x - size.x).max(0.0));
+
+            draw.rectangle(
+                cursor - vec2(0.0, size.y - 2.0),
+                vec2(size.x, 2.0),
+                style.frame_color,
+            );
+
+            draw.push_clip(cursor, size);
+
+            frame::render_lines(
+                draw,
+                cursor - vec2(offset, 0.0),
+                vec2(content_size.x.max(size.x), size.y),
+                Vec2::ZERO,
+                &strip_style,
+                &lines,
+                &line_sizes,
+            );
+
+            draw.pop_clip();
+        })),
+        update_with_max_width: None,
+    };
+
+    ui.current_line.push(strip);
+    ui.next_line();
+
+    // Without tabs there is no content to show
+    if !labels.is_empty() {
+        let content_id = id.with_child(Id::from_vec2(vec2(selected_tab as f32, 0.0)));
+        let content = frame::build(ui, true, style, None, None, content_id, true, |ui| {
+            func(ui, selected_tab)
+        });
+
+        ui.current_line.push(content);
+    }
+
+    response
+}
+
+impl Persist for TabsState {
+    const TAG: &'static str = "tabs";
+}
+
+impl fmt::Display for TabsState {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.offset)
+    }
+}
+
+impl FromStr for TabsState {
+    type Err = ();
+
+    fn from_str(text: &str) -> Result<TabsState, ()> {
+        Ok(TabsState {
+            offset: text.trim().parse().map_err(|_| ())?,
+            viewport_width: Rc::default(),
+            scrolled_to: None,
+        })
+    }
+}
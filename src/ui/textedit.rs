@@ -3,14 +3,19 @@ use crate::{
     math::{vec2, vec4, Vec2},
     ui::{
         color::{held_color, hover_color},
-        frame, Ui,
+        frame,
+        scroll_area::SCROLL_LINES_PER_STEP,
+        Style, Ui,
     },
     window::{Key, KeyRepeat, MouseButton, Window},
 };
-use std::{mem, time::Instant};
+use std::{mem, rc::Rc, time::Instant};
 
 use super::Align;
 
+const LINE_SPACING: f32 = 1.25;
+const PASSWORD_CHAR: char = '\u{2022}';
+
 const TEXTEDIT_K_SHIFT: i32 = 0x4000_0000;
 const TEXTEDIT_K_CONTROL: i32 = 0x2000_0000;
 
@@ -212,16 +217,48 @@ struct TexteditRow {
 
 impl TexteditRow {
     fn layout(&mut self, calc_text_width: &impl Fn(&str) -> f32, str: &mut TextEdit, start_i: i32) {
-        let width = calc_text_width(&str.string);
-        let remaining_chars = str.string.chars().count() as i32 - start_i;
-
-        self.num_chars = remaining_chars;
         self.x0 = 0.0;
-        self.x1 = width;
-        self.baseline_y_delta = 1.25;
-        self.ymin = -1.0;
-        self.ymax = 0.0;
+        self.baseline_y_delta = str.line_height;
+        self.ymin = 0.0;
+        self.ymax = str.line_height;
+
+        if str.single_line {
+            self.num_chars = string_len(&str.string) - start_i;
+            self.x1 = calc_text_width(&str.string);
+        } else {
+            let start = byte_index(&str.string, start_i);
+            let rest = &str.string[start..];
+            let (num_chars, end) = wrap_row(calc_text_width, rest, str.wrap_width);
+
+            self.num_chars = num_chars;
+            self.x1 = calc_text_width(&rest[..end]);
+        }
+    }
+}
+
+/// Finds where the row starting at `text` ends when wrapped to `width`.
+/// Returns the number of chars in the row, including a trailing newline,
+/// and the byte length of the visible part of the row.
+fn wrap_row(calc_text_width: &impl Fn(&str) -> f32, text: &str, width: f32) -> (i32, usize) {
+    let mut last_break = None;
+
+    for (count, (i, c)) in text.char_indices().enumerate() {
+        if c == '\n' {
+            return (count as i32 + 1, i);
+        }
+
+        let end = i + c.len_utf8();
+
+        if count > 0 && calc_text_width(&text[..end]) > width {
+            return last_break.unwrap_or((count as i32, i));
+        }
+
+        if is_space(c) {
+            last_break = Some((count as i32 + 1, end));
+        }
     }
+
+    (string_len(text), text.len())
 }
 
 #[derive(Copy, Clone, Default)]
@@ -328,6 +365,13 @@ fn string_len(string: &str) -> i32 {
     string.chars().count() as _
 }
 
+fn byte_index(string: &str, pos: i32) -> usize {
+    string
+        .char_indices()
+        .nth(pos as usize)
+        .map_or(string.len(), |(i, _)| i)
+}
+
 fn string_width(
     calc_text_width: impl Fn(&str) -> f32,
     str: &mut TextEdit,
@@ -366,11 +410,16 @@ fn is_space(c: char) -> bool {
     c.is_whitespace()
 }
 
+fn mask(text: &str) -> String {
+    text.chars().map(|_| PASSWORD_CHAR).collect()
+}
+
 #[derive(Clone)]
 pub struct TextEdit {
     pub string: String,
     limit: i32,
     active: bool,
+    focus_requested: bool,
     no_drag: bool,
     blink_timer: Instant,
 
@@ -382,6 +431,13 @@ pub struct TextEdit {
     has_preferred_x: bool,
     single_line: bool,
     preferred_x: f32,
+    wrap_width: f32,
+    line_height: f32,
+    scroll: f32,
+    password: bool,
+    placeholder: String,
+    filter: Option<Rc<dyn Fn(char) -> bool>>,
+    primary_selection: (i32, i32),
     undostate: UndoState,
 }
 
@@ -394,6 +450,7 @@ impl TextEdit {
             string,
             limit,
             active: false,
+            focus_requested: false,
             no_drag: false,
             blink_timer: Instant::now(),
             cursor: start_width,
@@ -404,6 +461,13 @@ impl TextEdit {
             single_line: true,
             insert_mode: false,
             row_count_per_page: 0,
+            wrap_width: 0.0,
+            line_height: 0.0,
+            scroll: 0.0,
+            password: false,
+            placeholder: String::new(),
+            filter: None,
+            primary_selection: (0, 0),
             undostate: UndoState {
                 undo_rec: [UndoRecord {
                     where_0: 0,
@@ -420,10 +484,61 @@ impl TextEdit {
         }
     }
 
+    /// Lets the text span several lines, wrapped to the width given to `show`.
+    pub fn multiline(mut self, multiline: bool) -> TextEdit {
+        self.single_line = !multiline;
+        self.password &= !multiline;
+        self
+    }
+
+    /// Draws every character as a bullet, password fields are always single line.
+    pub fn password(mut self, password: bool) -> TextEdit {
+        self.password = password;
+        self.single_line |= password;
+        self
+    }
+
+    /// Hint shown in `Style::inactive_color` while the field is empty.
+    pub fn placeholder(mut self, placeholder: impl Into<String>) -> TextEdit {
+        self.placeholder = placeholder.into();
+        self
+    }
+
+    /// Only characters for which `filter` returns true are inserted, when typed or pasted.
+    pub fn filter(mut self, filter: impl Fn(char) -> bool + 'static) -> TextEdit {
+        self.filter = Some(Rc::new(filter));
+        self
+    }
+
+    fn accepts(&self, c: char) -> bool {
+        self.filter.as_ref().is_none_or(|filter| filter(c))
+    }
+
+    #[inline]
+    pub(crate) fn focus(&mut self) {
+        self.active = true;
+        self.focus_requested = true;
+        self.no_drag = true;
+        self.blink_timer = Instant::now();
+    }
+
+    #[inline]
+    pub(crate) fn is_active(&self) -> bool {
+        self.active
+    }
+
     pub fn show(&mut self, size: Vec2, window: &Window, context: &Context, ui: &mut Ui) {
+        let id = ui.current_id();
         let response = ui.response();
         let style = ui.style;
 
+        // Keyboard focus is shared by all widgets, so only one edit is active at a time
+        if mem::take(&mut self.focus_requested) {
+            ui.state.focused = Some(id);
+        }
+
+        self.active = ui.state.focused == Some(id) && !ui.state.focus_blocked;
+
         let style = if response.held && !self.active {
             style.frame_color(held_color(style.frame_color))
         } else if response.hovered && !self.active {
@@ -435,32 +550,38 @@ impl TextEdit {
         let calc_text_width = {
             let text_height = style.text_height;
             let font_id = style.font.unwrap_or_default().id;
+            let password = self.password;
 
             move |s: &str| {
                 context.set_font_size(text_height as _);
-                let extent = context.text_extents(s).unwrap();
+                let extent = if password {
+                    context.text_extents(&mask(s)).unwrap()
+                } else {
+                    context.text_extents(s).unwrap()
+                };
                 extent.width() as f32
             }
         };
 
+        self.wrap_width = size.x;
+        self.line_height = style.text_height * LINE_SPACING;
+        self.row_count_per_page = ((size.y / self.line_height) as i32).max(1);
+
         let pressed = window.get_mouse_down(MouseButton::Left);
+        let previous_cursor = self.cursor;
+
+        let mouse_x = response.relative_mouse_pos.x - style.padding.left - style.margin.left;
+        let mouse_y =
+            -response.relative_mouse_pos.y - style.padding.top - style.margin.top + self.scroll;
 
         if response.double_clicked && self.active {
             self.select_start = 0;
             self.select_end = string_len(&self.string);
             self.no_drag = true;
         } else if response.pressed && self.active {
-            self.click(
-                &calc_text_width,
-                response.relative_mouse_pos.x - style.padding.left - style.margin.left,
-                response.relative_mouse_pos.y,
-            );
+            self.click(&calc_text_width, mouse_x, mouse_y);
         } else if response.held && self.active && !self.no_drag {
-            self.drag(
-                &calc_text_width,
-                response.relative_mouse_pos.x - style.padding.left - style.margin.left,
-                response.relative_mouse_pos.y,
-            );
+            self.drag(&calc_text_width, mouse_x, mouse_y);
         } else if response.released {
             self.active = true;
             self.no_drag = false;
@@ -468,6 +589,22 @@ impl TextEdit {
             self.active = false;
         }
 
+        if response.middle_clicked && !self.password {
+            if let Some(text) = window.get_primary_text() {
+                self.focus();
+                self.click(&calc_text_width, mouse_x, mouse_y);
+                self.paste_limited(&text);
+            }
+        }
+
+        self.focus_requested = false;
+
+        if !self.active && ui.state.focused == Some(id) {
+            ui.state.focused = None;
+        }
+
+        ui.focusable(id, self.active);
+
         if self.active {
             let pressed = window.get_keys_pressed(KeyRepeat::Yes);
 
@@ -492,29 +629,9 @@ impl TextEdit {
                 }
 
                 match (key, ctrl) {
-                    (Key::C | Key::X, true) => {
-                        if self.select_start != self.select_end {
-                            /*if let Ok(mut ctx) = ClipboardContext::new() {
-                                let start_char_idx = self.select_start.min(self.select_end);
-                                let end_char_idx = self.select_start.max(self.select_end);
-
-                                let start_byte_idx = self
-                                    .string
-                                    .char_indices()
-                                    .nth(start_char_idx as _)
-                                    .map(|(i, _)| i)
-                                    .unwrap_or(0);
-                                let end_byte_idx = self
-                                    .string
-                                    .char_indices()
-                                    .nth(end_char_idx as _)
-                                    .map(|(i, _)| i)
-                                    .unwrap_or(self.string.len());
-
-                                let selected = &self.string[start_byte_idx..end_byte_idx];
-
-                                ctx.set_contents(selected.to_owned()).ok();
-                            }*/
+                    (Key::C | Key::X, true) if !self.password => {
+                        if let Some(selected) = self.selected_text() {
+                            window.set_clipboard_text(selected);
 
                             if key == Key::X {
                                 self.cut();
@@ -522,15 +639,9 @@ impl TextEdit {
                         }
                     }
                     (Key::V, true) => {
-                        /*if let Ok(mut ctx) = ClipboardContext::new() {
-                            if let Ok(content) = ctx.get_contents() {
-                                self.paste(&content);
-
-                                if string_len(&self.string) > self.limit {
-                                    self.undo()
-                                }
-                            }
-                        }*/
+                        if let Some(text) = window.get_clipboard_text() {
+                            self.paste_limited(&text);
+                        }
                     }
                     _ => (),
                 }
@@ -539,6 +650,12 @@ impl TextEdit {
             let chars = window.chars();
 
             for char in chars {
+                let char = match char::from_u32(char) {
+                    Some('\r') => '\n',
+                    Some(c) if c == '\n' || !c.is_control() => c,
+                    _ => continue,
+                };
+
                 self.key(&calc_text_width, char as _);
 
                 if string_len(&self.string) > self.limit {
@@ -549,7 +666,37 @@ impl TextEdit {
             }
         }
 
-        let text = self.string.clone(); // TODO(JoNil) Lifetime so we con't have to clone!
+        if self.active && !self.password && self.select_start != self.select_end {
+            let selection = (self.select_start, self.select_end);
+
+            if self.primary_selection != selection {
+                if let Some(selected) = self.selected_text() {
+                    window.set_primary_text(selected);
+                }
+                self.primary_selection = selection;
+            }
+        } else {
+            self.primary_selection = (0, 0);
+        }
+
+        if !self.single_line {
+            self.show_multiline(
+                &calc_text_width,
+                size,
+                style,
+                response.hovered,
+                previous_cursor,
+                ui,
+            );
+            return;
+        }
+
+        let text = if self.password {
+            mask(&self.string)
+        } else {
+            self.string.clone() // TODO(JoNil) Lifetime so we con't have to clone!
+        };
+        let placeholder = self.placeholder.clone();
         let active = self.active;
         let cursor = self.cursor;
 
@@ -608,8 +755,14 @@ impl TextEdit {
 
         frame::show(ui, false, style, None, Some(ui.current_id()), true, |ui| {
             ui.canvas(size, move |draw, cursor, size| {
+                let (text, color) = if text.is_empty() {
+                    (&placeholder, style.inactive_color)
+                } else {
+                    (&text, style.text_color)
+                };
+
                 draw.text(
-                    &text,
+                    text,
                     cursor
                         + vec2(
                             0.0,
@@ -618,7 +771,7 @@ impl TextEdit {
                     vec2(size.x, style.text_height + 0.001),
                     style.text_height,
                     Align::Left,
-                    style.text_color,
+                    color,
                     style.font.unwrap_or_default(),
                 );
 
@@ -655,6 +808,160 @@ impl TextEdit {
         });
     }
 
+    fn show_multiline(
+        &mut self,
+        calc_text_width: &impl Fn(&str) -> f32,
+        size: Vec2,
+        style: Style,
+        hovered: bool,
+        previous_cursor: i32,
+        ui: &mut Ui,
+    ) {
+        let text_height = style.text_height;
+        let line_height = self.line_height;
+        let font = style.font.unwrap_or_default();
+
+        let mut rows = Vec::new();
+        let mut start = 0;
+        let mut start_char = 0;
+
+        while start < self.string.len() {
+            let rest = &self.string[start..];
+            let (num_chars, end) = wrap_row(calc_text_width, rest, self.wrap_width);
+
+            rows.push((start_char, &rest[..end]));
+
+            start += byte_index(rest, num_chars);
+            start_char += num_chars;
+        }
+
+        if self.string.is_empty() || self.string.ends_with('\n') {
+            rows.push((start_char, ""));
+        }
+
+        let max_scroll = (rows.len() as f32 * line_height - size.y).max(0.0);
+
+        if hovered {
+            let wheel = ui.state.scroll_wheel.y;
+
+            if max_scroll > 0.0 && wheel != 0.0 {
+                self.scroll -= wheel * text_height * SCROLL_LINES_PER_STEP;
+                ui.state.scroll_wheel.y = 0.0;
+            }
+        }
+
+        let cursor_row = rows
+            .iter()
+            .rposition(|(start, _)| *start <= self.cursor)
+            .unwrap_or(0);
+
+        if self.cursor != previous_cursor {
+            let cursor_top = cursor_row as f32 * line_height;
+
+            self.scroll = self
+                .scroll
+                .max(cursor_top + line_height - size.y)
+                .min(cursor_top);
+        }
+
+        self.scroll = self.scroll.clamp(0.0, max_scroll);
+
+        let text_width = |text: &str| ui.draw.calc_text_size(text, text_height, 10000.0, font).x;
+
+        let selection = (
+            self.select_start.min(self.select_end),
+            self.select_start.max(self.select_end),
+        );
+
+        let scroll = self.scroll;
+        let mut visible_rows = Vec::new();
+        let mut cursor_pos = None;
+
+        for (index, (start, text)) in rows.iter().enumerate() {
+            let top = index as f32 * line_height - scroll;
+
+            if top + line_height < 0.0 || top > size.y {
+                continue;
+            }
+
+            let end = start + string_len(text);
+
+            if index == cursor_row {
+                let before = &text[..byte_index(text, self.cursor - start)];
+                cursor_pos = Some(vec2(text_width(before), top));
+            }
+
+            let selected = if selection.0 < end && selection.1 > *start {
+                let from = byte_index(text, selection.0.max(*start) - start);
+                let to = byte_index(text, selection.1.min(end) - start);
+
+                Some((text_width(&text[..from]), text_width(&text[from..to])))
+            } else {
+                None
+            };
+
+            visible_rows.push((text.to_string(), top, selected));
+        }
+
+        if self.string.is_empty() {
+            if let Some(row) = visible_rows.first_mut() {
+                row.0 = self.placeholder.clone();
+            }
+        }
+
+        let empty = self.string.is_empty();
+        let active = self.active;
+        let blink_time = self.blink_timer.elapsed();
+        let center = (line_height - text_height) / 2.0;
+
+        frame::show(ui, false, style, None, Some(ui.current_id()), true, |ui| {
+            ui.canvas(size, move |draw, cursor, size| {
+                draw.push_clip(cursor, size);
+
+                let color = if empty {
+                    style.inactive_color
+                } else {
+                    style.text_color
+                };
+
+                for (text, top, selected) in &visible_rows {
+                    draw.text(
+                        text,
+                        cursor + vec2(0.0, -top - center - 0.1 * text_height / 2.0),
+                        vec2(size.x, text_height + 0.001),
+                        text_height,
+                        Align::Left,
+                        color,
+                        font,
+                    );
+
+                    if active {
+                        if let Some((before_width, selected_width)) = *selected {
+                            draw.rectangle(
+                                cursor
+                                    + vec2(before_width, -top - center + 0.1 * text_height / 2.0),
+                                vec2(selected_width, text_height),
+                                vec4(0.0, 0.0, 1.0, 0.2),
+                            );
+                        }
+                    }
+                }
+
+                if let Some(pos) = cursor_pos {
+                    if active && blink_time.as_millis() % 1000 < 500 {
+                        draw.rectangle(
+                            cursor + vec2(pos.x, -pos.y - center + 0.1 * text_height / 2.0),
+                            vec2(2.0, text_height),
+                            style.text_color,
+                        );
+                    }
+                }
+
+                draw.pop_clip();
+            });
+        });
+    }
+
     fn locate_coord(&mut self, calc_text_width: &impl Fn(&str) -> f32, x: f32, y: f32) -> i32 {
         let mut r = TexteditRow::default();
         let n = string_len(&self.string);
@@ -798,7 +1105,10 @@ impl TextEdit {
     }
 
     fn is_word_boundary(&mut self, idx: i32) -> bool {
-        if idx > 0 {
+        if self.password {
+            // Word jumps would give away where the spaces are.
+            idx == 0
+        } else if idx > 0 {
             is_space(get_char(&self.string, idx - 1)) && !is_space(get_char(&self.string, idx))
         } else {
             true
@@ -853,8 +1163,34 @@ impl TextEdit {
         false
     }
 
+    fn selected_text(&self) -> Option<&str> {
+        if self.select_start == self.select_end {
+            return None;
+        }
+
+        let start = byte_index(&self.string, self.select_start.min(self.select_end));
+        let end = byte_index(&self.string, self.select_start.max(self.select_end));
+
+        Some(&self.string[start..end])
+    }
+
+    fn paste_limited(&mut self, text: &str) {
+        self.paste(text);
+
+        if string_len(&self.string) > self.limit {
+            self.undo()
+        }
+
+        self.blink_timer = Instant::now();
+    }
+
     fn paste(&mut self, text: &str) {
-        let len = text.len() as _;
+        let text = text
+            .chars()
+            .filter(|&c| self.accepts(c) && !(c == '\n' && self.single_line))
+            .collect::<String>();
+        let text = text.as_str();
+        let len = string_len(text);
         self.clamp();
         self.delete_selection();
         insert_chars(&mut self.string, self.cursor, text);
@@ -1178,7 +1514,7 @@ impl TextEdit {
                         let mut ch_buf = [0; 4];
                         let ch = &*(c).encode_utf8(&mut ch_buf);
 
-                        if !(c == '\n' && self.single_line) {
+                        if !(c == '\n' && self.single_line) && self.accepts(c) {
                             if self.insert_mode
                                 && self.select_start == self.select_end
                                 && self.cursor < string_len(&self.string)
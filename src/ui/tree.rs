@@ -0,0 +1,289 @@
+use crate::{
+    math::{vec2, Vec2},
+    ui::{
+        collapsing::draw_arrow,
+        color::{held_color, hover_color},
+        frame,
+        id::Id,
+        FrameStyle, Response, Spacing, Ui,
+    },
+    window::{Key, KeyRepeat},
+};
+use std::{borrow::Cow, collections::HashMap, hash::Hash, mem};
+
+#[derive(Clone, Copy)]
+pub(crate) struct TreeRow {
+    id: Id,
+    parent: Option<Id>,
+    has_children: bool,
+}
+
+#[derive(Default)]
+pub(crate) struct TreeState {
+    selected: Option<Id>,
+    rows: Vec<TreeRow>,
+    /// Whether the tree was shown this frame, trees that weren't are dropped.
+    pub(crate) shown: bool,
+}
+
+pub struct Tree<'t, 'a, 'draw, 'show> {
+    ui: &'t mut Ui<'a, 'draw, 'show>,
+    parent: Option<Id>,
+    root: Id,
+    depth: usize,
+    selected: Option<Id>,
+    previous_selected: Option<Id>,
+    clicked: bool,
+    rows: Vec<TreeRow>,
+    labels: HashMap<Id, usize>,
+}
+
+pub(crate) fn show(ui: &mut Ui, id_source: impl Hash, func: impl FnOnce(&mut Tree)) {
+    let style = ui.style;
+    let id = ui.parent_id.with_child(Id::new(id_source));
+    let area_style = style.margin(Spacing::ZERO).padding(Spacing::ZERO);
+
+    frame::show(ui, true, area_style, None, None, true, |ui| {
+        ui.style = style;
+
+        let mut state = ui.state.trees.remove(&id).unwrap_or_default();
+        let previous_selected = state.selected;
+
+        // The arrow keys only move the selection while the tree has keyboard focus
+        if ui.focusable(id, false) {
+            navigate(ui, &mut state);
+        }
+
+        let mut tree = Tree {
+            ui,
+            parent: None,
+            root: id,
+            depth: 0,
+            selected: state.selected,
+            previous_selected,
+            clicked: false,
+            rows: Vec::new(),
+            labels: HashMap::new(),
+        };
+
+        func(&mut tree);
+
+        let clicked = tree.clicked;
+
+        state.selected = tree.selected;
+        state.rows = mem::take(&mut tree.rows);
+        state.shown = true;
+
+        if clicked {
+            ui.focusable(id, true);
+        }
+
+        ui.state.trees.insert(id, state);
+    });
+}
+
+/// Moves the selection with the arrow keys through the rows shown last frame.
+fn navigate(ui: &mut Ui, state: &mut TreeState) {
+    let window = ui.window;
+    let pressed = |key| window.is_key_pressed(key, KeyRepeat::Yes);
+
+    let index = state
+        .selected
+        .and_then(|selected| state.rows.iter().position(|row| row.id == selected));
+
+    let Some(index) = index else {
+        if pressed(Key::Down) || pressed(Key::Up) {
+            state.selected = state.rows.first().map(|row| row.id);
+        }
+        return;
+    };
+
+    let row = state.rows[index];
+    let expanded = *ui.memory.get_persisted_or_default::<bool>(row.id);
+
+    if pressed(Key::Down) {
+        if let Some(next) = state.rows.get(index + 1) {
+            state.selected = Some(next.id);
+        }
+    } else if pressed(Key::Up) {
+        if index > 0 {
+            state.selected = Some(state.rows[index - 1].id);
+        }
+    } else if pressed(Key::Right) {
+        if row.has_children && !expanded {
+            ui.memory.insert(row.id, true);
+        } else if let Some(child) = state.rows.get(index + 1) {
+            if child.parent == Some(row.id) {
+                state.selected = Some(child.id);
+            }
+        }
+    } else if pressed(Key::Left) {
+        if row.has_children && expanded {
+            ui.memory.insert(row.id, false);
+        } else if row.parent.is_some() {
+            state.selected = row.parent;
+        }
+    }
+}
+
+impl<'t, 'a, 'draw, 'show> Tree<'t, 'a, 'draw, 'show> {
+    /// A row with children, `func` is only called while the node is expanded.
+    pub fn node(
+        &mut self,
+        label: impl Into<Cow<'a, str>>,
+        func: impl FnOnce(&mut Tree<'t, 'a, 'draw, 'show>),
+    ) -> Response {
+        let label = label.into();
+        let id = self.row_id(&label);
+
+        let mut expanded = *self.ui.memory.get_persisted_or_default::<bool>(id);
+
+        let arrow_id = id.with_child(Id::new("arrow"));
+        let arrow = self.ui.response_for(arrow_id);
+
+        let response = self.ui.widget_response_for(id);
+
+        if arrow.pressed || response.double_clicked {
+            expanded = !expanded;
+            self.ui.memory.insert(id, expanded);
+        }
+
+        if arrow.pressed {
+            self.clicked = true;
+        }
+
+        let response = self.row(id, label, Some((arrow_id, expanded)));
+
+        if expanded {
+            let parent = self.parent.replace(id);
+            self.depth += 1;
+
+            func(self);
+
+            self.depth -= 1;
+            self.parent = parent;
+        }
+
+        response
+    }
+
+    pub fn leaf(&mut self, label: impl Into<Cow<'a, str>>) -> Response {
+        let label = label.into();
+        let id = self.row_id(&label);
+
+        self.row(id, label, None)
+    }
+
+    /// Rows are told apart by their label, siblings with the same label by how
+    /// many of them came before.
+    fn row_id(&mut self, label: &str) -> Id {
+        let id = self.parent.unwrap_or(self.root).with_child(Id::new(label));
+        let count = self.labels.entry(id).or_insert(0);
+
+        *count += 1;
+
+        match *count {
+            1 => id,
+            count => id.with_child(Id::new(count - 1)),
+        }
+    }
+
+    fn row(&mut self, id: Id, label: Cow<'a, str>, arrow: Option<(Id, bool)>) -> Response {
+        let ui = &mut *self.ui;
+        let style = ui.style;
+
+        self.rows.push(TreeRow {
+            id,
+            parent: self.parent,
+            has_children: arrow.is_some(),
+        });
+
+        let mut response = ui.widget_response_for(id);
+
+        if response.pressed {
+            self.selected = Some(id);
+            self.clicked = true;
+        }
+
+        let selected = self.selected == Some(id);
+        response.changed = selected && self.previous_selected != Some(id);
+
+        let row_style = if response.held || selected {
+            style.frame_color(held_color(style.frame_color))
+        } else if response.hovered {
+            style.frame_color(hover_color(style.frame_color))
+        } else {
+            style.frame_style(FrameStyle::None)
+        };
+
+        let foreground = if style.enabled {
+            style.text_color
+        } else {
+            style.inactive_color
+        };
+
+        let indent = style.text_height;
+        let depth = self.depth;
+        let guide_color = style.inactive_color;
+        let margin = style.margin;
+
+        let element = frame::build(
+            ui,
+            false,
+            row_style,
+            None,
+            style.enabled.then_some(id),
+            id,
+            true,
+            |ui| {
+                if depth > 0 {
+                    ui.canvas(
+                        vec2(depth as f32 * indent, style.text_height),
+                        move |draw, cursor, size| {
+                            for level in 0..depth {
+                                let x = cursor.x + (level as f32 + 0.5) * indent;
+
+                                draw.line(
+                                    vec2(x, cursor.y + margin.top + style.padding.top),
+                                    vec2(
+                                        x,
+                                        cursor.y - size.y - margin.bottom - style.padding.bottom,
+                                    ),
+                                    1.0,
+                                    guide_color,
+                                );
+                            }
+                        },
+                    );
+                }
+
+                let arrow_size = vec2(indent, style.text_height);
+
+                match arrow {
+                    Some((arrow_id, expanded)) => {
+                        ui.push_ui_element(
+                            style,
+                            arrow_size,
+                            style.enabled.then_some(arrow_id),
+                            Vec2::ZERO,
+                            false,
+                            false,
+                            move |draw, cursor, size| {
+                                draw_arrow(draw, cursor, size, expanded, foreground);
+                            },
+                        );
+                    }
+                    None => ui.empty_area(arrow_size),
+                }
+
+                ui.colored_text(label.into_owned(), foreground);
+                ui.horizontal_spring();
+            },
+        );
+
+        ui.current_line.push(element);
+        ui.next_line();
+
+        response
+    }
+}
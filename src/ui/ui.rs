@@ -1,15 +1,29 @@
 use crate::math::{vec2, vec4, Vec2, Vec4};
 use crate::ui::{
+    animation::{self, Animate, Easing},
+    bounding_box::BoundingBox,
+    collapsing,
     color::{held_color, hover_color},
+    combo_box,
     draw_api::DrawApi,
+    flex::{FlexColumn, FlexItem, FlexRow},
     frame,
+    grid::container_style,
     id::Id,
-    spacing, Font, Image, Response, Spacing, Style,
+    menu_bar, modal,
+    panel::{self, DockedPanel},
+    popup::{self, Overlay, Placement},
+    scroll_area, spacing, tabs, tree, virtual_list, DragValue, Flex, FloatingWindow, Font, Grid,
+    GridUi, Image, Memory, MenuBar, Numeric, OuiState, Panel, Response, Slider, Spacing, Split,
+    Style, Track, Tree,
 };
-use std::{borrow::Cow, cell::Cell, f32::INFINITY, mem, rc::Rc};
+use crate::window::{CursorStyle, Window};
+use std::{borrow::Cow, cell::Cell, f32::INFINITY, hash::Hash, mem, ops::RangeInclusive, rc::Rc};
 
 type Draw<'a> = dyn Fn(&mut DrawApi, Vec2, Vec2) + 'a;
-type UpdateWithMaxWidth = dyn Fn(Vec2);
+pub(crate) type UpdateWithMaxWidth = dyn Fn(Vec2);
+
+pub(crate) const FLEX_WEIGHT: Vec2 = vec2(1.0, 0.0);
 
 pub(crate) struct Element<'a> {
     pub(crate) content_box: Rc<Cell<Vec2>>,
@@ -17,18 +31,35 @@ pub(crate) struct Element<'a> {
     pub(crate) border_extra: Vec2,
     pub(crate) flex_x: bool,
     pub(crate) flex_y: bool,
+    /// Share of the extra width when `flex_x`, and of the extra height.
+    pub(crate) weight: Vec2,
+    pub(crate) max_size: Vec2,
     pub(crate) style: Style,
     pub(crate) render: Option<Box<Draw<'a>>>,
-    pub(crate) update_with_max_width: Option<Box<UpdateWithMaxWidth>>,
+    pub(crate) update_with_max_width: Option<Rc<UpdateWithMaxWidth>>,
+}
+
+#[derive(Clone, Copy)]
+struct IndicatorColors {
+    frame: Vec4,
+    foreground: Vec4,
+    inactive: Vec4,
 }
 
 pub struct Ui<'a, 'draw, 'show> {
     pub(crate) draw: &'show mut DrawApi<'draw>,
+    pub(crate) window: &'show Window,
     pub(crate) responses: &'show Vec<(Id, Response)>,
+    pub(crate) state: &'show mut OuiState,
+    pub(crate) memory: &'show mut Memory,
+    pub(crate) overlays: &'show mut Vec<Overlay<'a>>,
     pub(crate) style: Style,
     pub(crate) current_line: Vec<Element<'a>>,
     pub(crate) lines: Vec<Vec<Element<'a>>>,
     pub(crate) parent_id: Id,
+    pub(crate) id_origin: (usize, usize),
+    pub(crate) next_id: Option<(Id, usize, usize)>,
+    pub(crate) panels: Vec<DockedPanel<'a>>,
 }
 
 impl<'a, 'draw, 'show> Ui<'a, 'draw, 'show> {
@@ -36,6 +67,17 @@ impl<'a, 'draw, 'show> Ui<'a, 'draw, 'show> {
         self.style
     }
 
+    /// Asks for `cursor` this frame, see `OuiContext::cursor_style`.
+    #[inline]
+    pub fn set_cursor(&mut self, cursor: CursorStyle) {
+        self.state.cursor = cursor;
+    }
+
+    #[inline]
+    pub fn memory(&mut self) -> &mut Memory {
+        self.memory
+    }
+
     #[inline]
     pub fn with_style(&mut self, style: Style, func: impl FnOnce(&mut Ui)) {
         let old = self.style;
@@ -159,6 +201,8 @@ impl<'a, 'draw, 'show> Ui<'a, 'draw, 'show> {
             border_extra: Vec2::ZERO,
             flex_x: false,
             flex_y: false,
+            weight: FLEX_WEIGHT,
+            max_size: Vec2::INFINITY,
             style: style.margin(Spacing::ZERO).padding(Spacing::ZERO),
             render: None,
             update_with_max_width: None,
@@ -175,6 +219,8 @@ impl<'a, 'draw, 'show> Ui<'a, 'draw, 'show> {
             border_extra: Vec2::ZERO,
             flex_x: false,
             flex_y: false,
+            weight: FLEX_WEIGHT,
+            max_size: Vec2::INFINITY,
             style: style.margin(Spacing::ZERO).padding(Spacing::ZERO),
             render: None,
             update_with_max_width: None,
@@ -196,6 +242,103 @@ impl<'a, 'draw, 'show> Ui<'a, 'draw, 'show> {
         frame::show(self, false, style, None, None, false, func);
     }
 
+    /// Places every widget built in `func` below the previous one.
+    pub fn vertical(&mut self, func: impl FnOnce(&mut Ui)) {
+        let style = self.style;
+
+        frame::show(self, true, container_style(style), None, None, true, |ui| {
+            ui.style = style;
+            func(ui);
+            ui.stack_vertically();
+        });
+    }
+
+    /// `count` columns of equal width side by side, `func` fills the column at `index`.
+    pub fn columns(&mut self, count: usize, mut func: impl FnMut(&mut Ui, usize)) {
+        let start = self.current_line.len();
+
+        for index in 0..count {
+            self.vertical(|ui| func(ui, index));
+        }
+
+        let columns = &mut self.current_line[start..];
+        let width = columns
+            .iter()
+            .map(|column| column.content_box.get().x)
+            .fold(0.0, f32::max);
+
+        for column in columns {
+            let extra_size = vec2(width - column.content_box.get().x, 0.0);
+
+            column
+                .content_box
+                .set(column.content_box.get() + extra_size);
+
+            if let Some(update_with_max_width) = column.update_with_max_width.as_ref() {
+                update_with_max_width(extra_size);
+            }
+
+            // Growing all columns by the same amount keeps them equally wide
+            column.flex_x = true;
+        }
+    }
+
+    #[inline]
+    pub fn grid(&mut self, columns: &[Track], func: impl FnOnce(&mut GridUi)) {
+        Grid::new(columns).show(self, func);
+    }
+
+    #[inline]
+    pub fn top_panel(&mut self, id_source: impl Hash, func: impl FnOnce(&mut Ui)) {
+        Panel::top(id_source).show(self, func);
+    }
+
+    #[inline]
+    pub fn bottom_panel(&mut self, id_source: impl Hash, func: impl FnOnce(&mut Ui)) {
+        Panel::bottom(id_source).show(self, func);
+    }
+
+    #[inline]
+    pub fn left_panel(&mut self, id_source: impl Hash, func: impl FnOnce(&mut Ui)) {
+        Panel::left(id_source).show(self, func);
+    }
+
+    #[inline]
+    pub fn right_panel(&mut self, id_source: impl Hash, func: impl FnOnce(&mut Ui)) {
+        Panel::right(id_source).show(self, func);
+    }
+
+    /// A `FloatingWindow`, returns false once it is closed.
+    #[inline]
+    pub fn window(
+        &mut self,
+        id_source: impl Hash,
+        title: impl Into<Cow<'a, str>>,
+        func: impl FnOnce(&mut Ui),
+    ) -> bool {
+        FloatingWindow::new(id_source, title).show(self, func)
+    }
+
+    #[inline]
+    pub fn horizontal_split(
+        &mut self,
+        id_source: impl Hash,
+        left: impl FnOnce(&mut Ui),
+        right: impl FnOnce(&mut Ui),
+    ) {
+        Split::horizontal(id_source).show(self, left, right);
+    }
+
+    #[inline]
+    pub fn vertical_split(
+        &mut self,
+        id_source: impl Hash,
+        top: impl FnOnce(&mut Ui),
+        bottom: impl FnOnce(&mut Ui),
+    ) {
+        Split::vertical(id_source).show(self, top, bottom);
+    }
+
     #[inline]
     pub fn no_expand_area(&mut self, style: Style, func: impl FnOnce(&mut Ui)) {
         frame::show(self, true, style, None, None, false, func);
@@ -211,6 +354,11 @@ impl<'a, 'draw, 'show> Ui<'a, 'draw, 'show> {
         frame::show(self, true, style, Some(size), None, false, func);
     }
 
+    #[inline]
+    pub fn scroll_area(&mut self, size: Vec2, style: Style, func: impl FnOnce(&mut Ui)) {
+        scroll_area::show(self, style, size, func);
+    }
+
     #[inline]
     pub fn canvas(&mut self, content_box: Vec2, draw: impl Fn(&mut DrawApi, Vec2, Vec2) + 'a) {
         self.push_ui_element(
@@ -252,17 +400,20 @@ impl<'a, 'draw, 'show> Ui<'a, 'draw, 'show> {
 
     #[inline]
     fn button_inner(&mut self, size: Option<Vec2>, func: impl FnOnce(&mut Ui)) -> Response {
-        let response = self.response();
+        let id = self.current_id();
+        let response = self.focusable_response_for(id, self.response());
         let style = self.style;
 
-        let style = if response.held {
-            style.frame_color(held_color(style.frame_color))
+        let frame_color = if response.held {
+            held_color(style.frame_color)
         } else if response.hovered {
-            style.frame_color(hover_color(style.frame_color))
+            hover_color(style.frame_color)
         } else {
-            style
+            style.frame_color
         };
 
+        let style = style.frame_color(self.fade_color(id, frame_color));
+
         frame::show(
             self,
             false,
@@ -290,7 +441,7 @@ impl<'a, 'draw, 'show> Ui<'a, 'draw, 'show> {
 
     #[inline]
     pub fn image_button(&mut self, image: Image) -> Response {
-        let response = self.response();
+        let response = self.focusable_response_for(self.current_id(), self.response());
         let style = self.style;
         let height = style.text_height;
         let width = height * image.width as f32 / image.height as f32;
@@ -312,25 +463,520 @@ impl<'a, 'draw, 'show> Ui<'a, 'draw, 'show> {
         })
     }
 
+    pub fn checkbox(&mut self, checked: &mut bool, label: impl Into<Cow<'a, str>>) -> Response {
+        let mut response = self.focusable_response();
+
+        if response.pressed {
+            *checked = !*checked;
+            response.changed = true;
+        }
+
+        let checked = *checked;
+
+        self.labeled_indicator(
+            label.into(),
+            1.0,
+            response,
+            move |draw, cursor, size, colors| {
+                let thickness = size.y / 10.0;
+
+                draw.rectangle_rounded(cursor, size, size.y / 5.0, colors.foreground);
+                draw.rectangle_rounded(
+                    cursor + vec2(thickness, -thickness),
+                    size - 2.0 * thickness,
+                    size.y / 5.0 - thickness,
+                    colors.frame,
+                );
+
+                if checked {
+                    let width = size.y / 8.0;
+
+                    draw.line(
+                        cursor + vec2(0.22 * size.x, -0.5 * size.y),
+                        cursor + vec2(0.42 * size.x, -0.72 * size.y),
+                        width,
+                        colors.foreground,
+                    );
+                    draw.line(
+                        cursor + vec2(0.42 * size.x, -0.72 * size.y),
+                        cursor + vec2(0.78 * size.x, -0.28 * size.y),
+                        width,
+                        colors.foreground,
+                    );
+                }
+            },
+        )
+    }
+
+    pub fn radio_value<T: PartialEq>(
+        &mut self,
+        current: &mut T,
+        value: T,
+        label: impl Into<Cow<'a, str>>,
+    ) -> Response {
+        let mut response = self.focusable_response();
+
+        let selected = *current == value;
+
+        if response.pressed && !selected {
+            *current = value;
+            response.changed = true;
+        }
+
+        let selected = selected || response.changed;
+
+        self.labeled_indicator(
+            label.into(),
+            1.0,
+            response,
+            move |draw, cursor, size, colors| {
+                let radius = size.y / 2.0;
+                let thickness = size.y / 10.0;
+
+                draw.circle(cursor, radius / 2.0, radius, colors.foreground);
+                draw.circle(
+                    cursor + vec2(thickness, -thickness),
+                    (radius - thickness) / 2.0,
+                    radius - thickness,
+                    colors.frame,
+                );
+
+                if selected {
+                    draw.circle(
+                        cursor + vec2(radius / 2.0, -radius / 2.0),
+                        radius / 4.0,
+                        radius / 2.0,
+                        colors.foreground,
+                    );
+                }
+            },
+        )
+    }
+
+    pub fn toggle(&mut self, on: &mut bool, label: impl Into<Cow<'a, str>>) -> Response {
+        let mut response = self.focusable_response();
+
+        if response.pressed {
+            *on = !*on;
+            response.changed = true;
+        }
+
+        let on = *on;
+        let enabled = self.style.enabled;
+
+        self.labeled_indicator(
+            label.into(),
+            1.8,
+            response,
+            move |draw, cursor, size, colors| {
+                let radius = size.y / 2.0;
+                let thickness = size.y / 10.0;
+
+                let track_color = if on && enabled {
+                    colors.foreground
+                } else {
+                    colors.inactive
+                };
+
+                draw.rectangle_rounded(cursor, size, radius, track_color);
+
+                let knob_x = if on {
+                    size.x - size.y + thickness
+                } else {
+                    thickness
+                };
+
+                draw.circle(
+                    cursor + vec2(knob_x, -thickness),
+                    (radius - thickness) / 2.0,
+                    radius - thickness,
+                    colors.frame,
+                );
+            },
+        )
+    }
+
+    /// Shows `text` next to the pointer once it has rested on the widget that
+    /// produced `response` for `Style::tooltip_delay` seconds.
+    #[inline]
+    pub fn tooltip(&mut self, response: &Response, text: impl Into<Cow<'a, str>>) {
+        let text = text.into().into_owned();
+        self.tooltip_ui(response, move |ui| ui.text(text));
+    }
+
+    pub fn tooltip_ui(&mut self, response: &Response, func: impl FnOnce(&mut Ui)) {
+        if !response.hovered || response.held {
+            return;
+        }
+
+        let style = self.style;
+
+        let shown = self
+            .state
+            .hover_start
+            .get(&response.id)
+            .is_some_and(|start| start.elapsed().as_secs_f32() >= style.tooltip_delay);
+
+        if !shown {
+            return;
+        }
+
+        let anchor = BoundingBox::new(self.state.mouse_pos, vec2(0.0, style.text_height));
+
+        popup::show(
+            self,
+            response.id.with_child(Id::new("tooltip")),
+            Placement::Anchored(Rc::new(Cell::new(anchor))),
+            false,
+            style,
+            func,
+        );
+    }
+
+    /// Shows a dialog centered on the screen that dims and blocks everything
+    /// behind it. Returns `false` once the user dismissed it with Escape.
+    #[inline]
+    pub fn modal(
+        &mut self,
+        id_source: impl Hash,
+        style: Style,
+        func: impl FnOnce(&mut Ui),
+    ) -> bool {
+        modal::show(self, id_source, style, func)
+    }
+
+    /// Shows a tab strip above the content of the selected tab. Clicking a tab
+    /// writes its index to `selected`, an index past the last tab selects the
+    /// last one. `func` isn't called when there are no tabs.
+    #[inline]
+    pub fn tabs(
+        &mut self,
+        id_source: impl Hash,
+        selected: &mut usize,
+        labels: &[impl AsRef<str>],
+        func: impl FnOnce(&mut Ui, usize),
+    ) -> Response {
+        tabs::show(self, id_source, selected, labels, func)
+    }
+
+    /// A header that shows or hides `func` below it when clicked.
+    #[inline]
+    pub fn collapsing(
+        &mut self,
+        id_source: impl Hash,
+        title: impl Into<Cow<'a, str>>,
+        func: impl FnOnce(&mut Ui),
+    ) -> Response {
+        collapsing::show(self, id_source, title.into(), func)
+    }
+
+    /// Clicking a row or tabbing to the tree gives it keyboard focus, the arrow
+    /// keys then move the selection and expand or collapse nodes.
+    #[inline]
+    pub fn tree_view(&mut self, id_source: impl Hash, func: impl FnOnce(&mut Tree)) {
+        tree::show(self, id_source, func);
+    }
+
+    /// Only builds the rows of `count` that are on screen, each `row_height` tall.
+    #[inline]
+    pub fn virtual_list(
+        &mut self,
+        row_height: f32,
+        count: usize,
+        func: impl FnMut(&mut Ui, usize),
+    ) {
+        virtual_list::show(self, row_height, count, func);
+    }
+
+    #[inline]
+    pub fn combo_box(
+        &mut self,
+        id_source: impl Hash,
+        selected: &mut usize,
+        options: &[impl AsRef<str>],
+    ) -> Response {
+        combo_box::show(self, id_source, selected, options)
+    }
+
+    /// Moves smoothly from the current value of the animation `id` to `target` over
+    /// `duration` seconds, restarting whenever `target` changes.
+    #[inline]
+    pub fn animate_value<T: Animate>(
+        &mut self,
+        id: Id,
+        target: T,
+        duration: f32,
+        easing: Easing,
+    ) -> T {
+        animation::animate_value(self, id, target, duration, easing)
+    }
+
+    /// Fades to the hover or press color over `Style::animation_time`.
+    #[inline]
+    pub(crate) fn fade_color(&mut self, id: Id, color: Vec4) -> Vec4 {
+        let duration = self.style.animation_time;
+
+        if duration > 0.0 {
+            self.animate_value(id, color, duration, Easing::EaseOut)
+        } else {
+            color
+        }
+    }
+
+    /// A row of menus, "&File" opens with Alt+F or a click and the arrow keys move
+    /// between neighbouring menus while one is open.
+    #[inline]
+    pub fn menu_bar(&mut self, func: impl FnOnce(&mut MenuBar)) {
+        menu_bar::show(self, func)
+    }
+
+    pub fn selectable_label(&mut self, selected: bool, text: impl Into<Cow<'a, str>>) -> Response {
+        let response = self.focusable_response();
+        let style = self.style;
+
+        let frame_color = if response.held || selected {
+            held_color(style.frame_color)
+        } else if response.hovered {
+            hover_color(style.frame_color)
+        } else {
+            style.frame_color
+        };
+
+        let frame_color = self.fade_color(self.current_id(), frame_color);
+
+        let color = if style.enabled {
+            style.text_color
+        } else {
+            style.inactive_color
+        };
+
+        let text = text.into().into_owned();
+
+        frame::show(
+            self,
+            false,
+            style.frame_color(frame_color),
+            None,
+            style.enabled.then(|| self.current_id()),
+            true,
+            move |ui| {
+                ui.colored_text(text, color);
+                ui.horizontal_spring();
+            },
+        );
+
+        response
+    }
+
+    #[inline]
+    pub fn slider<T: Numeric>(&mut self, value: &mut T, range: RangeInclusive<T>) -> Response {
+        Slider::new(value, range).show(self)
+    }
+
+    #[inline]
+    pub fn drag_value<T: Numeric>(&mut self, value: &mut T) -> Response {
+        DragValue::new(value).show(self)
+    }
+
+    #[inline]
+    pub(crate) fn widget_response(&self) -> Response {
+        self.widget_response_for(self.current_id())
+    }
+
+    #[inline]
+    pub(crate) fn widget_response_for(&self, id: Id) -> Response {
+        if self.style.enabled {
+            self.response_for(id)
+        } else {
+            Response::default()
+        }
+    }
+
+    #[inline]
+    pub(crate) fn focusable_response(&mut self) -> Response {
+        self.focusable_response_for(self.current_id(), self.widget_response())
+    }
+
+    /// Enter or Space on the focused widget counts as a press.
+    pub(crate) fn focusable_response_for(&mut self, id: Id, mut response: Response) -> Response {
+        response.focused = self.focusable(id, response.pressed);
+
+        if response.focused && self.state.focus_activated {
+            response.pressed = true;
+            response.released = true;
+        }
+
+        response
+    }
+
+    /// Lets Tab reach `id` and focuses it when pressed, returns whether it has keyboard focus.
+    pub(crate) fn focusable(&mut self, id: Id, pressed: bool) -> bool {
+        if !self.style.enabled || self.state.focus_blocked {
+            return false;
+        }
+
+        if pressed {
+            self.state.focused = Some(id);
+        }
+
+        // An inline edit registers again under the id of the value it replaces
+        if self.state.focusables.last() != Some(&id) {
+            self.state.focusables.push(id);
+        }
+
+        self.state.focused == Some(id)
+    }
+
+    fn labeled_indicator(
+        &mut self,
+        label: Cow<'a, str>,
+        indicator_aspect: f32,
+        response: Response,
+        draw_indicator: impl Fn(&mut DrawApi, Vec2, Vec2, IndicatorColors) + 'a,
+    ) -> Response {
+        let style = self.style;
+        let text_height = style.text_height;
+        let font = style.font.unwrap_or_default();
+
+        let indicator_size = vec2(indicator_aspect * text_height, text_height);
+        let gap = if label.is_empty() {
+            0.0
+        } else {
+            text_height / 3.0
+        };
+
+        let text_size = self
+            .draw
+            .calc_text_size(label.as_ref(), text_height, f32::INFINITY, font);
+
+        let content_box = vec2(
+            indicator_size.x + gap + text_size.x,
+            indicator_size.y.max(text_size.y),
+        );
+
+        let colors = IndicatorColors {
+            frame: self.fade_color(
+                self.current_id(),
+                if response.held {
+                    held_color(style.frame_color)
+                } else if response.hovered {
+                    hover_color(style.frame_color)
+                } else {
+                    style.frame_color
+                },
+            ),
+            foreground: if style.enabled {
+                style.text_color
+            } else {
+                style.inactive_color
+            },
+            inactive: style.inactive_color,
+        };
+
+        self.push_ui_element(
+            style,
+            content_box,
+            style.enabled.then(|| self.current_id()),
+            Vec2::ZERO,
+            false,
+            false,
+            move |draw, cursor, size| {
+                draw_indicator(
+                    draw,
+                    cursor - vec2(0.0, (size.y - indicator_size.y) / 2.0),
+                    indicator_size,
+                    colors,
+                );
+
+                draw.text(
+                    label.as_ref(),
+                    cursor + vec2(indicator_size.x + gap, -(size.y - text_size.y) / 2.0),
+                    text_size,
+                    text_height,
+                    super::Align::Left,
+                    colors.foreground,
+                    font,
+                );
+            },
+        );
+
+        response
+    }
+
+    /// Gives the widgets built in `func` ids derived from `id_source` instead of
+    /// their position, so they keep their state when items before them come and go.
+    pub fn push_id(&mut self, id_source: impl Hash, func: impl FnOnce(&mut Ui)) {
+        let parent_id = self.parent_id;
+        let id_origin = self.id_origin;
+
+        self.parent_id = self.parent_id.with_child(Id::new(id_source));
+        self.id_origin = (self.lines.len(), self.current_line.len());
+        self.next_id = None;
+
+        func(self);
+
+        self.parent_id = parent_id;
+        self.id_origin = id_origin;
+    }
+
+    /// Sets the id of the next widget, `ui.id_source(robot.id).button("Stop")`.
+    #[inline]
+    pub fn id_source(&mut self, id_source: impl Hash) -> &mut Self {
+        self.next_id = Some((
+            self.parent_id.with_child(Id::new(id_source)),
+            self.lines.len(),
+            self.current_line.len(),
+        ));
+        self
+    }
+
     #[inline]
     pub(crate) fn current_id(&self) -> Id {
-        self.parent_id.with_child(Id::from_vec2(vec2(
-            self.current_line.len() as _,
-            self.lines.len() as _,
-        )))
+        let line = self.lines.len();
+        let column = self.current_line.len();
+
+        if let Some((id, _, _)) = self
+            .next_id
+            .filter(|next| (next.1, next.2) == (line, column))
+        {
+            return id;
+        }
+
+        // Positions count from where the innermost `push_id` started
+        let (origin_line, origin_column) = self.id_origin;
+        let column = if line == origin_line {
+            column - origin_column
+        } else {
+            column
+        };
+
+        self.parent_id
+            .with_child(Id::from_vec2(vec2(column as _, (line - origin_line) as _)))
+    }
+
+    #[inline]
+    pub(crate) fn is_dragging(&self, id: Id) -> bool {
+        self.state.mouse_pressed && self.state.mouse_pressed_id == id
     }
 
     #[inline]
     pub fn response(&self) -> Response {
+        self.response_for(self.current_id())
+    }
+
+    #[inline]
+    pub(crate) fn response_for(&self, id: Id) -> Response {
         self.responses
             .iter()
-            .find_map(|(id, r)| {
-                if *id == self.current_id() {
-                    Some(r)
-                } else {
-                    None
-                }
-            })
+            .find_map(
+                |(response_id, r)| {
+                    if *response_id == id {
+                        Some(r)
+                    } else {
+                        None
+                    }
+                },
+            )
             .copied()
             .unwrap_or_default()
     }
@@ -353,12 +999,25 @@ impl<'a, 'draw, 'show> Ui<'a, 'draw, 'show> {
             border_extra,
             flex_x,
             flex_y,
+            weight: FLEX_WEIGHT,
+            max_size: Vec2::INFINITY,
             style,
             render: Some(Box::new(render)),
             update_with_max_width: None,
         });
     }
 
+    fn stack_vertically(&mut self) {
+        let mut lines = mem::take(&mut self.lines);
+        lines.push(mem::take(&mut self.current_line));
+
+        self.lines = lines
+            .into_iter()
+            .flatten()
+            .map(|element| vec![element])
+            .collect();
+    }
+
     #[inline]
     pub fn horizontal_spring(&mut self) {
         self.current_line.push(Element {
@@ -367,10 +1026,40 @@ impl<'a, 'draw, 'show> Ui<'a, 'draw, 'show> {
             border_extra: Vec2::ZERO,
             flex_x: true,
             flex_y: false,
+            weight: FLEX_WEIGHT,
+            max_size: Vec2::INFINITY,
+            style: self.style.margin(Spacing::ZERO).padding(Spacing::ZERO),
+            render: None,
+            update_with_max_width: None,
+        });
+    }
+
+    /// Takes a line of its own and pushes the lines below it to the bottom.
+    #[inline]
+    pub fn vertical_spring(&mut self) {
+        if !self.current_line.is_empty() {
+            self.next_line();
+        }
+
+        self.current_line.push(Element {
+            content_box: Rc::new(Cell::new(Vec2::ZERO)),
+            id: None,
+            border_extra: Vec2::ZERO,
+            flex_x: false,
+            flex_y: true,
+            weight: vec2(0.0, 1.0),
+            max_size: Vec2::INFINITY,
             style: self.style.margin(Spacing::ZERO).padding(Spacing::ZERO),
             render: None,
             update_with_max_width: None,
         });
+
+        self.next_line();
+    }
+
+    #[inline]
+    pub fn flex(&mut self, weight: f32, func: impl FnOnce(&mut Ui)) {
+        Flex::new().weight(weight).show(self, func);
     }
 
     #[allow(clippy::type_complexity)]
@@ -384,91 +1073,107 @@ impl<'a, 'draw, 'show> Ui<'a, 'draw, 'show> {
     ) {
         self.lines.push(self.current_line);
 
-        if self.lines.len() > 1 && self.lines.last().unwrap().is_empty() {
-            self.lines.pop();
+        // Docked panels surround everything else, which becomes the central area
+        if !self.panels.is_empty() {
+            let (lines, line_sizes, total_size, flex_children) = layout_lines(self.lines);
+
+            self.lines = vec![vec![panel::dock(
+                self.panels,
+                self.style,
+                lines,
+                line_sizes,
+                total_size,
+                flex_children,
+            )]];
         }
 
-        let mut line_sizes = Vec::new();
-        let mut total_size = Vec2::ZERO;
+        layout_lines(self.lines)
+    }
+}
 
-        for line in &self.lines {
-            let mut line_size = Vec2::ZERO;
+#[allow(clippy::type_complexity)]
+fn layout_lines(
+    mut lines: Vec<Vec<Element>>,
+) -> (
+    Vec<Vec<Element>>,
+    Vec<Rc<Cell<Vec2>>>,
+    Vec2,
+    Vec<Box<UpdateWithMaxWidth>>,
+) {
+    if lines.len() > 1 && lines.last().unwrap().is_empty() {
+        lines.pop();
+    }
 
-            for element in line.iter() {
-                let bounding_box = spacing::bounding_box(
-                    element.content_box.get(),
-                    element.style.margin,
-                    element.style.padding,
-                );
+    let mut line_sizes = Vec::new();
+    let mut total_size = Vec2::ZERO;
 
-                line_size.x += bounding_box.width();
-                line_size.y = line_size.y.max(bounding_box.height());
-            }
+    for line in &lines {
+        let mut line_size = Vec2::ZERO;
 
-            line_sizes.push(Rc::new(Cell::new(line_size)));
+        for element in line.iter() {
+            let bounding_box = spacing::bounding_box(
+                element.content_box.get(),
+                element.style.margin,
+                element.style.padding,
+            );
 
-            total_size.x = total_size.x.max(line_size.x);
-            total_size.y += line_size.y;
+            line_size.x += bounding_box.width();
+            line_size.y = line_size.y.max(bounding_box.height());
         }
 
-        let mut flex_children = Vec::<Box<UpdateWithMaxWidth>>::new();
+        line_sizes.push(Rc::new(Cell::new(line_size)));
 
-        for (line, line_size) in self.lines.iter_mut().zip(line_sizes.iter_mut()) {
-            let mut flex_count = 0;
+        total_size.x = total_size.x.max(line_size.x);
+        total_size.y += line_size.y;
+    }
 
-            for element in line.iter() {
-                if element.flex_x {
-                    flex_count += 1;
-                }
-            }
+    let mut rows = Vec::new();
+    let mut column = FlexColumn::new();
 
-            if flex_count > 0 {
-                let extra_line_width = total_size.x - line_size.get().x;
-                let extra_width_per_flex = extra_line_width / flex_count as f32;
+    for (line, line_size) in lines.iter().zip(line_sizes.iter()) {
+        let items = line
+            .iter()
+            .filter(|element| element.flex_x)
+            .map(|element| FlexItem::new(element, vec2(1.0, 0.0)))
+            .collect::<Vec<_>>();
+
+        if !items.is_empty() {
+            let row = FlexRow::new(items, line_size.clone());
+            row.grow(total_size.x - line_size.get().x);
+            rows.push(row);
+        }
 
-                let mut line_flex_elements = Vec::new();
+        let weight = line
+            .iter()
+            .map(|element| element.weight.y)
+            .fold(0.0, f32::max);
 
-                for element in line {
-                    if element.flex_x {
-                        let extra_size = vec2(extra_width_per_flex, 0.0);
+        if weight > 0.0 {
+            let items = line
+                .iter()
+                .filter(|element| element.weight.y > 0.0)
+                .map(|element| FlexItem::new(element, vec2(0.0, 1.0)))
+                .collect();
 
-                        element
-                            .content_box
-                            .set(element.content_box.get() + extra_size);
+            column.push(weight, line_size.clone(), items);
+        }
+    }
 
-                        if let Some(update_with_max_width) = element.update_with_max_width.as_ref()
-                        {
-                            update_with_max_width(extra_size);
-                        }
+    let mut flex_children = Vec::<Box<UpdateWithMaxWidth>>::new();
 
-                        line_flex_elements.push((
-                            element.content_box.clone(),
-                            element.update_with_max_width.take(),
-                        ));
-                    }
+    if !rows.is_empty() || !column.is_empty() {
+        flex_children.push(Box::new(move |extra_size: Vec2| {
+            if extra_size.x != 0.0 {
+                for row in &rows {
+                    row.grow(extra_size.x);
                 }
-
-                flex_children.push(Box::new({
-                    let line_size = line_size.clone();
-                    move |extra_size: Vec2| {
-                        let extra_size = vec2(extra_size.x / flex_count as f32, extra_size.y);
-
-                        for (content_box, update_with_max_width) in &line_flex_elements {
-                            content_box.set(content_box.get() + extra_size);
-
-                            if let Some(update_with_max_width) = update_with_max_width.as_ref() {
-                                update_with_max_width(extra_size);
-                            }
-                        }
-
-                        line_size.set(line_size.get() + extra_size);
-                    }
-                }));
-
-                line_size.set(line_size.get() + vec2(extra_line_width, 0.0));
             }
-        }
 
-        (self.lines, line_sizes, total_size, flex_children)
+            if extra_size.y != 0.0 && !column.is_empty() {
+                column.grow(extra_size.y);
+            }
+        }));
     }
+
+    (lines, line_sizes, total_size, flex_children)
 }
@@ -0,0 +1,136 @@
+use crate::{
+    math::{vec2, Vec2},
+    ui::{
+        bounding_box::BoundingBox,
+        frame,
+        id::Id,
+        ui::{Element, FLEX_WEIGHT},
+        Spacing, Ui,
+    },
+};
+use std::{cell::Cell, rc::Rc};
+
+/// The rows a list built this frame, and the rows it found on screen when it
+/// was rendered. The next frame builds the rows that were on screen.
+pub(crate) struct VirtualListState {
+    visible: Cell<(usize, usize)>,
+    built: Cell<(usize, usize)>,
+}
+
+impl VirtualListState {
+    /// Whether the list was scrolled to rows it didn't build, so another frame
+    /// is needed to show them.
+    #[inline]
+    pub(crate) fn is_stale(&self) -> bool {
+        self.visible.get() != self.built.get()
+    }
+}
+
+pub(crate) fn show(
+    ui: &mut Ui,
+    row_height: f32,
+    count: usize,
+    mut func: impl FnMut(&mut Ui, usize),
+) {
+    let style = ui.style;
+    let id = ui.current_id();
+    let row_height = row_height.max(1.0);
+    let screen_size = ui.state.screen_size;
+
+    let list = ui
+        .state
+        .virtual_lists
+        .entry(id)
+        .or_insert_with(|| {
+            Rc::new(VirtualListState {
+                visible: Cell::new((0, (screen_size.y / row_height).ceil() as usize)),
+                built: Cell::new((0, 0)),
+            })
+        })
+        .clone();
+
+    let (first, last) = list.visible.get();
+    let first = first.min(count);
+    let last = last.clamp(first, count);
+
+    list.built.set((first, last));
+
+    let row_style = style.margin(Spacing::ZERO).padding(Spacing::ZERO);
+    let parent_id = ui.parent_id.with_child(id);
+
+    let rows = (first..last)
+        .map(|index| {
+            frame::build(
+                ui,
+                true,
+                row_style,
+                Some(vec2(0.0, row_height)),
+                None,
+                parent_id.with_child(Id::from_vec2(vec2(0.0, index as f32))),
+                true,
+                |ui| func(ui, index),
+            )
+        })
+        .collect::<Vec<Element>>();
+
+    let width = rows
+        .iter()
+        .map(|row| row.content_box.get().x)
+        .fold(0.0, f32::max);
+
+    ui.current_line.push(Element {
+        content_box: Rc::new(Cell::new(vec2(width, count as f32 * row_height))),
+        id: None,
+        border_extra: Vec2::ZERO,
+        flex_x: rows.iter().any(|row| row.flex_x),
+        flex_y: false,
+        weight: FLEX_WEIGHT,
+        max_size: Vec2::INFINITY,
+        style,
+        render: Some(Box::new(move |draw, cursor, size| {
+            if draw.pass() != 1 {
+                return;
+            }
+
+            let region = draw
+                .clip_rect()
+                .unwrap_or(BoundingBox::new(Vec2::ZERO, screen_size));
+
+            let top = ((cursor.y - region.top_left.y) / row_height)
+                .floor()
+                .max(0.0);
+            let bottom = ((cursor.y - region.top_left.y + region.size.y) / row_height).ceil();
+            let overscan = ((bottom - top) / 2.0).ceil();
+
+            let first = ((top - overscan).max(0.0) as usize).min(count);
+            let last = ((bottom + overscan).max(0.0) as usize).clamp(first, count);
+
+            list.visible.set((first, last));
+
+            let (first, last) = list.built.get();
+
+            for (index, row) in (first..last).zip(&rows) {
+                let content_box = row.content_box.get();
+
+                if content_box.x < size.x {
+                    let extra_size = vec2(size.x - content_box.x, 0.0);
+
+                    row.content_box.set(content_box + extra_size);
+
+                    if let Some(update_with_max_width) = &row.update_with_max_width {
+                        update_with_max_width(extra_size);
+                    }
+                }
+
+                if let Some(render) = row.render.as_ref() {
+                    render(
+                        draw,
+                        cursor - vec2(0.0, index as f32 * row_height),
+                        row.content_box.get(),
+                    );
+                }
+            }
+        })),
+        update_with_max_width: None,
+    });
+}
@@ -28,6 +28,7 @@ pub type HANDLE = *mut c_void;
 pub type HBRUSH = *mut HBRUSH__;
 pub type HCURSOR = HICON;
 pub type HDC = *mut HDC__;
+pub type HGLOBAL = HANDLE;
 pub type HGDIOBJ = *mut c_void;
 pub type HICON = *mut HICON__;
 pub type HINSTANCE = *mut HINSTANCE__;
@@ -42,6 +43,7 @@ pub type LPRECT = *mut RECT;
 pub type LPVOID = *mut c_void;
 pub type LPWSTR = *mut WCHAR;
 pub type LRESULT = LONG_PTR;
+pub type SIZE_T = usize;
 pub type LPPOINT = *mut POINT;
 pub type WNDPROC = Option<unsafe extern "system" fn(HWND, UINT, WPARAM, LPARAM) -> LRESULT>;
 pub type WPARAM = UINT_PTR;
@@ -114,12 +116,14 @@ pub struct MSG {
 }
 
 pub const BI_BITFIELDS: DWORD = 3;
+pub const CF_UNICODETEXT: UINT = 13;
 pub const CS_HREDRAW: UINT = 0x0002;
 pub const CS_OWNDC: UINT = 0x0020;
 pub const CS_VREDRAW: UINT = 0x0001;
 pub const CW_USEDEFAULT: c_int = -2147483648;
 pub const ICON_SMALL: UINT = 0;
 pub const DIB_RGB_COLORS: DWORD = 0;
+pub const GMEM_MOVEABLE: UINT = 0x0002;
 pub const WM_SETICON: UINT = 0x0080;
 pub const GWLP_USERDATA: c_int = -21;
 pub const HTCLIENT: LRESULT = 1;
@@ -188,6 +192,7 @@ pub fn LOWORD(l: DWORD) -> WORD {
 #[link(name = "User32")]
 extern "system" {
     pub fn AdjustWindowRect(lpRect: LPRECT, dwStyle: DWORD, bMenu: BOOL) -> BOOL;
+    pub fn CloseClipboard() -> BOOL;
     pub fn CreateSolidBrush(color: COLORREF) -> HBRUSH;
     pub fn CreateWindowExW(
         dwExStyle: DWORD,
@@ -207,7 +212,9 @@ extern "system" {
     pub fn DeleteObject(ho: HGDIOBJ) -> BOOL;
     pub fn DestroyWindow(hWnd: HWND) -> BOOL;
     pub fn DispatchMessageW(lpmsg: *const MSG) -> LRESULT;
+    pub fn EmptyClipboard() -> BOOL;
     pub fn GetActiveWindow() -> HWND;
+    pub fn GetClipboardData(uFormat: UINT) -> HANDLE;
     pub fn GetCursorPos(lpPoint: LPPOINT) -> BOOL;
     pub fn GetDC(hWnd: HWND) -> HDC;
     pub fn GetFullPathNameW(
@@ -229,6 +236,7 @@ extern "system" {
         cy: c_int,
         fuLoad: UINT,
     ) -> HANDLE;
+    pub fn OpenClipboard(hWndNewOwner: HWND) -> BOOL;
     pub fn PeekMessageW(
         lpMsg: LPMSG,
         hWnd: HWND,
@@ -241,6 +249,7 @@ extern "system" {
     pub fn ScreenToClient(hWnd: HWND, lpPoint: LPPOINT) -> BOOL;
     pub fn SelectObject(hdc: HDC, h: HGDIOBJ) -> HGDIOBJ;
     pub fn SendMessageW(hWnd: HWND, Msg: UINT, wParam: WPARAM, lParam: LPARAM) -> LRESULT;
+    pub fn SetClipboardData(uFormat: UINT, hMem: HANDLE) -> HANDLE;
     pub fn SetCursor(hCursor: HCURSOR) -> HCURSOR;
     pub fn SetWindowPos(
         hWnd: HWND,
@@ -291,4 +300,8 @@ extern "system" {
 #[link(name = "Kernel32")]
 extern "system" {
     pub fn GetModuleHandleA(lpModuleName: LPCSTR) -> HMODULE;
+    pub fn GlobalAlloc(uFlags: UINT, dwBytes: SIZE_T) -> HGLOBAL;
+    pub fn GlobalFree(hMem: HGLOBAL) -> HGLOBAL;
+    pub fn GlobalLock(hMem: HGLOBAL) -> LPVOID;
+    pub fn GlobalUnlock(hMem: HGLOBAL) -> BOOL;
 }
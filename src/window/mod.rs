@@ -604,6 +604,55 @@ impl Window {
         self.0.set_cursor_style(cursor)
     }
 
+    /// Put text on the system clipboard, other applications can paste it for as long as the
+    /// window is open.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use mini_ui::window::*;
+    /// # let window = Window::new("Test", 640, 400, WindowOptions::default()).unwrap();
+    /// window.set_clipboard_text("192.168.0.1");
+    /// ```
+    #[inline]
+    pub fn set_clipboard_text(&self, text: &str) {
+        self.0.set_clipboard_text(text)
+    }
+
+    /// Get the text on the system clipboard, `None` if it's empty or doesn't hold text.
+    ///
+    /// On X11 the text is requested from the application that owns the clipboard, this blocks
+    /// until it answers and gives up after 200 ms without a reply, or between the parts of a
+    /// large (INCR) transfer.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use mini_ui::window::*;
+    /// # let window = Window::new("Test", 640, 400, WindowOptions::default()).unwrap();
+    /// if let Some(text) = window.get_clipboard_text() {
+    ///     println!("clipboard: {}", text);
+    /// }
+    /// ```
+    #[inline]
+    pub fn get_clipboard_text(&self) -> Option<String> {
+        self.0.get_clipboard_text()
+    }
+
+    /// Set the primary selection, the text X11 pastes on middle click.
+    /// Usually the last text the user selected. Other platforms have no primary
+    /// selection, there it is only shared within the process.
+    #[inline]
+    pub fn set_primary_text(&self, text: &str) {
+        self.0.set_primary_text(text)
+    }
+
+    /// Get the primary selection, the text X11 pastes on middle click.
+    #[inline]
+    pub fn get_primary_text(&self) -> Option<String> {
+        self.0.get_primary_text()
+    }
+
     /// Get the current keys that are down.
     ///
     /// # Examples
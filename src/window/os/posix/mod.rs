@@ -24,10 +24,11 @@ use crate::{
     },
 };
 use std::{
+    cell::RefCell,
     convert::TryFrom,
     ffi::{c_char, c_int, c_long, c_uchar, c_uint, c_ulong, c_void, CStr, CString},
     mem::MaybeUninit,
-    time::Duration,
+    time::{Duration, Instant},
 };
 
 mod scaler;
@@ -37,6 +38,23 @@ mod xcursor;
 const Button6: c_uint = xlib::Button5 + 1;
 const Button7: c_uint = xlib::Button5 + 2;
 
+// How long to wait for another client to hand over the selection, or the next part of it,
+// before giving up
+const SELECTION_TIMEOUT: Duration = Duration::from_millis(200);
+
+const POLLIN: i16 = 0x0001;
+
+#[repr(C)]
+struct PollFd {
+    fd: c_int,
+    events: i16,
+    revents: i16,
+}
+
+extern "C" {
+    fn poll(fds: *mut PollFd, count: c_ulong, timeout: c_int) -> c_int;
+}
+
 #[repr(C)]
 struct MwmHints {
     flags: c_ulong,
@@ -58,6 +76,11 @@ struct DisplayInfo {
     cursors: [xlib::Cursor; 8],
     keyb_ext: bool,
     wm_delete_window: xlib::Atom,
+    clipboard: xlib::Atom,
+    utf8_string: xlib::Atom,
+    targets: xlib::Atom,
+    selection_property: xlib::Atom,
+    incr: xlib::Atom,
 }
 
 impl DisplayInfo {
@@ -133,6 +156,11 @@ impl DisplayInfo {
                 cursors: [0; 8],
                 keyb_ext: false,
                 wm_delete_window: 0,
+                clipboard: 0,
+                utf8_string: 0,
+                targets: 0,
+                selection_property: 0,
+                incr: 0,
             })
         }
     }
@@ -210,6 +238,11 @@ impl DisplayInfo {
 
     fn init_atoms(&mut self) {
         self.wm_delete_window = self.intern_atom(b"WM_DELETE_WINDOW\0", false);
+        self.clipboard = self.intern_atom(b"CLIPBOARD\0", false);
+        self.utf8_string = self.intern_atom(b"UTF8_STRING\0", false);
+        self.targets = self.intern_atom(b"TARGETS\0", false);
+        self.selection_property = self.intern_atom(b"MINI_UI_SELECTION\0", false);
+        self.incr = self.intern_atom(b"INCR\0", false);
     }
 
     fn intern_atom(&mut self, name: &'static [u8], only_if_exists: bool) -> xlib::Atom {
@@ -271,6 +304,10 @@ pub struct Window {
 
     key_handler: KeyHandler,
     update_rate: UpdateRate,
+
+    // Text we serve while we own the CLIPBOARD and PRIMARY selections
+    clipboard: RefCell<String>,
+    primary: RefCell<String>,
 }
 
 impl Window {
@@ -387,7 +424,8 @@ impl Window {
                     | xlib::KeyReleaseMask
                     | xlib::ButtonPressMask
                     | xlib::ButtonReleaseMask
-                    | xlib::FocusChangeMask,
+                    | xlib::FocusChangeMask
+                    | xlib::PropertyChangeMask,
             );
 
             if !opts.resize || opts.none {
@@ -467,6 +505,8 @@ impl Window {
                 active: false,
                 key_handler: KeyHandler::new(),
                 update_rate: UpdateRate::new(),
+                clipboard: RefCell::new(String::new()),
+                primary: RefCell::new(String::new()),
             })
         }
     }
@@ -596,6 +636,248 @@ impl Window {
         }
     }
 
+    #[inline]
+    pub fn set_clipboard_text(&self, text: &str) {
+        self.set_selection(self.d.clipboard, text);
+    }
+
+    #[inline]
+    pub fn get_clipboard_text(&self) -> Option<String> {
+        self.get_selection(self.d.clipboard)
+    }
+
+    #[inline]
+    pub fn set_primary_text(&self, text: &str) {
+        self.set_selection(xlib::XA_PRIMARY, text);
+    }
+
+    #[inline]
+    pub fn get_primary_text(&self) -> Option<String> {
+        self.get_selection(xlib::XA_PRIMARY)
+    }
+
+    fn selection_text(&self, selection: xlib::Atom) -> &RefCell<String> {
+        if selection == xlib::XA_PRIMARY {
+            &self.primary
+        } else {
+            &self.clipboard
+        }
+    }
+
+    fn set_selection(&self, selection: xlib::Atom, text: &str) {
+        *self.selection_text(selection).borrow_mut() = text.to_owned();
+
+        unsafe {
+            xlib::XSetSelectionOwner(self.d.display, selection, self.handle, xlib::CurrentTime);
+            xlib::XFlush(self.d.display);
+        }
+    }
+
+    fn get_selection(&self, selection: xlib::Atom) -> Option<String> {
+        unsafe {
+            let owner = xlib::XGetSelectionOwner(self.d.display, selection);
+
+            if owner == self.handle {
+                return Some(self.selection_text(selection).borrow().clone());
+            }
+
+            if owner == 0 {
+                return None;
+            }
+
+            xlib::XConvertSelection(
+                self.d.display,
+                selection,
+                self.d.utf8_string,
+                self.d.selection_property,
+                self.handle,
+                xlib::CurrentTime,
+            );
+            xlib::XFlush(self.d.display);
+
+            // The owner answers with a SelectionNotify once the text is stored in our property
+            let event = self.wait_for_event(xlib::SelectionNotify, |_| true)?;
+
+            if event.selection.property == 0 {
+                return None;
+            }
+
+            // Drop the notifications from the owner storing the property, so only the parts of an
+            // INCR transfer are waited on below
+            let mut stale: XEvent = std::mem::zeroed();
+
+            while xlib::XCheckTypedWindowEvent(
+                self.d.display,
+                self.handle,
+                xlib::PropertyNotify,
+                &mut stale,
+            ) != xlib::False
+            {}
+
+            let (kind, bytes) = self.read_property(event.selection.property)?;
+
+            if kind == self.d.incr {
+                return self.read_incremental_text(event.selection.property);
+            }
+
+            bytes.map(|bytes| String::from_utf8_lossy(&bytes).into_owned())
+        }
+    }
+
+    /// Large selections arrive in parts, deleting the property asks the owner for the next one
+    /// and an empty part ends the transfer.
+    unsafe fn read_incremental_text(&self, property: xlib::Atom) -> Option<String> {
+        // Parts may split a character, so the text is decoded once at the end
+        let mut bytes = Vec::new();
+
+        loop {
+            self.wait_for_event(xlib::PropertyNotify, |event| {
+                event.property.atom == property && event.property.state == xlib::PropertyNewValue
+            })?;
+
+            match self.read_property(property)?.1 {
+                Some(part) if !part.is_empty() => bytes.extend_from_slice(&part),
+                Some(_) => return Some(String::from_utf8_lossy(&bytes).into_owned()),
+                None => return None,
+            }
+        }
+    }
+
+    /// Waits on the connection for an event of `event_type` sent to this window that `accept`
+    /// takes, other events stay queued for the event loop.
+    unsafe fn wait_for_event(
+        &self,
+        event_type: c_int,
+        accept: impl Fn(&XEvent) -> bool,
+    ) -> Option<XEvent> {
+        let deadline = Instant::now() + SELECTION_TIMEOUT;
+        let mut event: XEvent = std::mem::zeroed();
+
+        loop {
+            while xlib::XCheckTypedWindowEvent(self.d.display, self.handle, event_type, &mut event)
+                != xlib::False
+            {
+                if accept(&event) {
+                    return Some(event);
+                }
+            }
+
+            let remaining = deadline.checked_duration_since(Instant::now())?;
+
+            let mut fd = PollFd {
+                fd: xlib::XConnectionNumber(self.d.display),
+                events: POLLIN,
+                revents: 0,
+            };
+
+            if poll(&mut fd, 1, remaining.as_millis().max(1) as c_int) < 0 {
+                return None;
+            }
+        }
+    }
+
+    /// Reads and deletes a property, giving its type and its bytes if it holds text.
+    unsafe fn read_property(&self, property: xlib::Atom) -> Option<(xlib::Atom, Option<Vec<u8>>)> {
+        let mut actual_type = 0;
+        let mut actual_format = 0;
+        let mut item_count = 0;
+        let mut bytes_after = 0;
+        let mut data = std::ptr::null_mut();
+
+        let status = xlib::XGetWindowProperty(
+            self.d.display,
+            self.handle,
+            property,
+            0,
+            c_long::from(i32::MAX) / 4,
+            xlib::True,
+            xlib::AnyPropertyType,
+            &mut actual_type,
+            &mut actual_format,
+            &mut item_count,
+            &mut bytes_after,
+            &mut data,
+        );
+
+        if status != 0 || data.is_null() {
+            return None;
+        }
+
+        let is_text = actual_type == self.d.utf8_string || actual_type == xlib::XA_STRING;
+
+        let bytes = (is_text && actual_format == 8)
+            .then(|| std::slice::from_raw_parts(data, item_count as usize).to_vec());
+
+        xlib::XFree(data as *mut c_void);
+
+        // Deleting the property is what lets the owner send the next part
+        xlib::XFlush(self.d.display);
+
+        Some((actual_type, bytes))
+    }
+
+    unsafe fn answer_selection_request(&self, request: &xlib::XSelectionRequestEvent) {
+        // Obsolete clients leave the property empty and expect the target to be used instead
+        let property = if request.property == 0 {
+            request.target
+        } else {
+            request.property
+        };
+
+        let mut reply = xlib::XSelectionEvent {
+            type_: xlib::SelectionNotify,
+            serial: 0,
+            send_event: xlib::True,
+            display: request.display,
+            requestor: request.requestor,
+            selection: request.selection,
+            target: request.target,
+            property,
+            time: request.time,
+        };
+
+        if request.target == self.d.targets {
+            let targets = [self.d.targets, self.d.utf8_string, xlib::XA_STRING];
+
+            xlib::XChangeProperty(
+                self.d.display,
+                request.requestor,
+                property,
+                xlib::XA_ATOM,
+                32,
+                xlib::PropModeReplace,
+                targets.as_ptr() as *const c_uchar,
+                targets.len() as c_int,
+            );
+        } else if request.target == self.d.utf8_string || request.target == xlib::XA_STRING {
+            let text = self.selection_text(request.selection).borrow();
+
+            xlib::XChangeProperty(
+                self.d.display,
+                request.requestor,
+                property,
+                request.target,
+                8,
+                xlib::PropModeReplace,
+                text.as_ptr(),
+                text.len() as c_int,
+            );
+        } else {
+            reply.property = 0;
+        }
+
+        let mut event = xlib::XEvent { selection: reply };
+
+        xlib::XSendEvent(
+            self.d.display,
+            request.requestor,
+            xlib::False,
+            xlib::NoEventMask,
+            &mut event,
+        );
+        xlib::XFlush(self.d.display);
+    }
+
     #[inline]
     pub fn get_window_handle(&self) -> *mut c_void {
         self.handle as *mut c_void
@@ -1018,6 +1300,14 @@ impl Window {
                 )
                 .expect("todo");
             }
+            xlib::SelectionRequest => {
+                self.answer_selection_request(&ev.selection_request);
+            }
+            xlib::SelectionClear => {
+                self.selection_text(ev.selection_clear.selection)
+                    .borrow_mut()
+                    .clear();
+            }
             xlib::FocusOut => {
                 self.active = false;
             }
@@ -7,6 +7,7 @@ use crate::{
     },
 };
 use std::{
+    cell::RefCell,
     ffi::{c_void, OsStr},
     os::windows::ffi::OsStrExt,
     time::Duration,
@@ -454,6 +455,9 @@ pub struct Window {
     cursors: [win32::HCURSOR; 8],
     draw_params: DrawParameters,
     mouse: MouseData,
+
+    // Windows has no primary selection, it is only shared within the process
+    primary: RefCell<String>,
 }
 
 impl Window {
@@ -574,6 +578,7 @@ impl Window {
 
             let window = Window {
                 mouse: MouseData::default(),
+                primary: RefCell::new(String::new()),
                 dc: win32::GetDC(handle.unwrap()),
                 window: Some(handle.unwrap()),
                 key_handler: KeyHandler::new(),
@@ -776,6 +781,83 @@ impl Window {
         self.cursor = cursor;
     }
 
+    pub fn set_clipboard_text(&self, text: &str) {
+        // Other applications expect lines to end with \r\n
+        let text = to_wstring(&text.replace("\r\n", "\n").replace('\n', "\r\n"));
+
+        unsafe {
+            if win32::OpenClipboard(self.window.unwrap_or(std::ptr::null_mut())) == 0 {
+                return;
+            }
+
+            win32::EmptyClipboard();
+
+            let memory = win32::GlobalAlloc(
+                win32::GMEM_MOVEABLE,
+                text.len() * std::mem::size_of::<u16>(),
+            );
+
+            if !memory.is_null() {
+                let data = win32::GlobalLock(memory) as *mut u16;
+
+                if data.is_null() {
+                    win32::GlobalFree(memory);
+                } else {
+                    std::ptr::copy_nonoverlapping(text.as_ptr(), data, text.len());
+                    win32::GlobalUnlock(memory);
+
+                    // The clipboard owns the memory once it has taken it
+                    if win32::SetClipboardData(win32::CF_UNICODETEXT, memory).is_null() {
+                        win32::GlobalFree(memory);
+                    }
+                }
+            }
+
+            win32::CloseClipboard();
+        }
+    }
+
+    pub fn get_clipboard_text(&self) -> Option<String> {
+        let mut text = None;
+
+        unsafe {
+            if win32::OpenClipboard(self.window.unwrap_or(std::ptr::null_mut())) == 0 {
+                return None;
+            }
+
+            let memory = win32::GetClipboardData(win32::CF_UNICODETEXT);
+
+            if !memory.is_null() {
+                let data = win32::GlobalLock(memory) as *const u16;
+
+                if !data.is_null() {
+                    let len = (0..).take_while(|&i| *data.add(i) != 0).count();
+
+                    text = Some(String::from_utf16_lossy(std::slice::from_raw_parts(
+                        data, len,
+                    )));
+                    win32::GlobalUnlock(memory);
+                }
+            }
+
+            win32::CloseClipboard();
+        }
+
+        // Other applications end lines with \r\n
+        text.map(|text| text.replace("\r\n", "\n"))
+            .filter(|text| !text.is_empty())
+    }
+
+    #[inline]
+    pub fn set_primary_text(&self, text: &str) {
+        *self.primary.borrow_mut() = text.to_owned();
+    }
+
+    #[inline]
+    pub fn get_primary_text(&self) -> Option<String> {
+        Some(self.primary.borrow().clone()).filter(|text| !text.is_empty())
+    }
+
     #[inline]
     pub fn set_rate(&mut self, rate: Option<Duration>) {
         self.update_rate.set_rate(rate);
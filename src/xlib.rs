@@ -1,6 +1,6 @@
 #![allow(non_upper_case_globals, clippy::upper_case_acronyms)]
 
-use std::ffi::{c_char, c_int, c_long, c_uchar, c_uint, c_ulong, c_ushort};
+use std::ffi::{c_char, c_int, c_long, c_uchar, c_uint, c_ulong, c_ushort, c_void};
 
 pub enum _XDisplay {}
 pub enum _XGC {}
@@ -144,6 +144,60 @@ pub struct XClientMessageEvent {
     pub data: ClientMessageData,
 }
 
+#[repr(C)]
+#[derive(Clone, Copy)]
+pub struct XSelectionRequestEvent {
+    pub type_: c_int,
+    pub serial: c_ulong,
+    pub send_event: Bool,
+    pub display: *mut Display,
+    pub owner: Window,
+    pub requestor: Window,
+    pub selection: Atom,
+    pub target: Atom,
+    pub property: Atom,
+    pub time: Time,
+}
+
+#[repr(C)]
+#[derive(Clone, Copy)]
+pub struct XSelectionEvent {
+    pub type_: c_int,
+    pub serial: c_ulong,
+    pub send_event: Bool,
+    pub display: *mut Display,
+    pub requestor: Window,
+    pub selection: Atom,
+    pub target: Atom,
+    pub property: Atom,
+    pub time: Time,
+}
+
+#[repr(C)]
+#[derive(Clone, Copy)]
+pub struct XSelectionClearEvent {
+    pub type_: c_int,
+    pub serial: c_ulong,
+    pub send_event: Bool,
+    pub display: *mut Display,
+    pub window: Window,
+    pub selection: Atom,
+    pub time: Time,
+}
+
+#[repr(C)]
+#[derive(Clone, Copy)]
+pub struct XPropertyEvent {
+    pub type_: c_int,
+    pub serial: c_ulong,
+    pub send_event: Bool,
+    pub display: *mut Display,
+    pub window: Window,
+    pub atom: Atom,
+    pub time: Time,
+    pub state: c_int,
+}
+
 #[repr(C)]
 #[derive(Clone, Copy)]
 pub union XEvent {
@@ -153,6 +207,10 @@ pub union XEvent {
     pub configure: XConfigureEvent,
     pub button: XButtonEvent,
     pub key: XKeyEvent,
+    pub selection_request: XSelectionRequestEvent,
+    pub selection: XSelectionEvent,
+    pub selection_clear: XSelectionClearEvent,
+    pub property: XPropertyEvent,
     pub pad: [c_long; 24],
 }
 
@@ -382,6 +440,7 @@ pub struct XColor {
 }
 
 pub const AllocNone: c_int = 0;
+pub const AnyPropertyType: Atom = 0;
 pub const Button1: c_uint = 1;
 pub const Button2: c_uint = 2;
 pub const Button3: c_uint = 3;
@@ -397,6 +456,7 @@ pub const CWBackingStore: c_ulong = 0x0040;
 pub const CWBackPixel: c_ulong = 0x0002;
 pub const CWBorderPixel: c_ulong = 0x0008;
 pub const CWColormap: c_ulong = 0x2000;
+pub const CurrentTime: Time = 0;
 pub const False: Bool = 0;
 pub const FocusChangeMask: c_long = 0x0020_0000;
 pub const FocusIn: c_int = 9;
@@ -406,11 +466,18 @@ pub const KeyPress: c_int = 2;
 pub const KeyPressMask: c_long = 0x0000_0001;
 pub const KeyRelease: c_int = 3;
 pub const KeyReleaseMask: c_long = 0x0000_0002;
+pub const NoEventMask: c_long = 0;
 pub const NoSymbol: c_int = 0;
 pub const NotUseful: c_int = 0;
 pub const PMaxSize: c_long = 0x0020;
 pub const PMinSize: c_long = 0x0010;
+pub const PropertyChangeMask: c_long = 0x0040_0000;
+pub const PropertyNewValue: c_int = 0;
+pub const PropertyNotify: c_int = 28;
 pub const PropModeReplace: c_int = 0;
+pub const SelectionClear: c_int = 29;
+pub const SelectionNotify: c_int = 31;
+pub const SelectionRequest: c_int = 30;
 pub const StructureNotifyMask: c_long = 0x0002_0000;
 pub const True: Bool = 1;
 pub const TrueColor: c_int = 4;
@@ -521,6 +588,9 @@ pub const XK_w: c_uint = 0x077;
 pub const XK_x: c_uint = 0x078;
 pub const XK_y: c_uint = 0x079;
 pub const XK_z: c_uint = 0x07a;
+pub const XA_ATOM: Atom = 4;
+pub const XA_PRIMARY: Atom = 1;
+pub const XA_STRING: Atom = 31;
 pub const XNClientWindow_0: &[u8] = b"clientWindow\0";
 pub const XNFocusWindow_0: &[u8] = b"focusWindow\0";
 pub const XNInputStyle_0: &[u8] = b"inputStyle\0";
@@ -664,6 +734,7 @@ extern "C" {
     ) -> c_int;
     pub fn XNextEvent(_2: *mut Display, _1: *mut XEvent) -> c_int;
     pub fn XPending(_1: *mut Display) -> c_int;
+    pub fn XConnectionNumber(_1: *mut Display) -> c_int;
     pub fn XFilterEvent(_2: *mut XEvent, _1: c_ulong) -> c_int;
     pub fn XkbKeycodeToKeysym(_4: *mut Display, _3: c_uchar, _2: c_int, _1: c_int) -> c_ulong;
     pub fn XLookupKeysym(_2: *mut XKeyEvent, _1: c_int) -> c_ulong;
@@ -677,5 +748,43 @@ extern "C" {
     ) -> c_int;
     pub fn XDestroyIC(_1: XIC);
     pub fn XCloseIM(_1: XIM) -> c_int;
+    pub fn XSetSelectionOwner(_4: *mut Display, _3: c_ulong, _2: c_ulong, _1: c_ulong) -> c_int;
+    pub fn XGetSelectionOwner(_2: *mut Display, _1: c_ulong) -> c_ulong;
+    pub fn XConvertSelection(
+        _6: *mut Display,
+        _5: c_ulong,
+        _4: c_ulong,
+        _3: c_ulong,
+        _2: c_ulong,
+        _1: c_ulong,
+    ) -> c_int;
+    pub fn XCheckTypedWindowEvent(
+        _4: *mut Display,
+        _3: c_ulong,
+        _2: c_int,
+        _1: *mut XEvent,
+    ) -> c_int;
+    pub fn XGetWindowProperty(
+        _12: *mut Display,
+        _11: c_ulong,
+        _10: c_ulong,
+        _9: c_long,
+        _8: c_long,
+        _7: c_int,
+        _6: c_ulong,
+        _5: *mut c_ulong,
+        _4: *mut c_int,
+        _3: *mut c_ulong,
+        _2: *mut c_ulong,
+        _1: *mut *mut c_uchar,
+    ) -> c_int;
+    pub fn XSendEvent(
+        _5: *mut Display,
+        _4: c_ulong,
+        _3: c_int,
+        _2: c_long,
+        _1: *mut XEvent,
+    ) -> c_int;
+    pub fn XFree(_1: *mut c_void) -> c_int;
 
 }